@@ -8,10 +8,15 @@ interface SeoCheckResult {
   error?: string;
 }
 
+interface Score {
+  value: number;
+  max: number;
+}
+
 interface SeoReport {
   url: string;
   timestamp: string;
-  score: number;
+  score: Score | null;
   summary: {
     total: number;
     passed: number;
@@ -514,7 +519,7 @@ function App() {
                 {new Date(timestamp).toLocaleString()}
               </div>
             </div>
-            {renderScoreGauge(score)}
+            {score && renderScoreGauge(Math.round((score.value / score.max) * 100))}
           </div>
 
           <div className="stats">
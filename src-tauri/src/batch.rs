@@ -0,0 +1,361 @@
+use crate::error::SeoError;
+use crate::process::ProcessRegistry;
+use crate::webhook::{Webhook, WebhookFormat};
+use crate::{execute_check, validate, CheckRequest, SeoCheckResult};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::{AcquireError, OwnedSemaphorePermit, Semaphore};
+use tokio::task::JoinError;
+use tokio::time::Instant;
+
+/// Bounds for the default number of CLI processes a batch runs at once.
+/// Each check is a Node.js process driving a browser, so more than one per
+/// core just thrashes.
+const MIN_CONCURRENT: usize = 2;
+const MAX_CONCURRENT: usize = 8;
+
+/// Gap between the starts of two checks on the same host when the caller
+/// doesn't say.
+pub const DEFAULT_HOST_DELAY: Duration = Duration::from_millis(500);
+
+/// Spaces out checks on the same host so a batch doesn't trip its rate
+/// limits, while checks on different hosts run as usual.
+#[derive(Clone)]
+pub struct HostThrottle {
+    min_delay: Duration,
+    /// When the latest check claimed for each host may start.
+    next_start: Arc<Mutex<HashMap<String, Instant>>>,
+}
+
+impl HostThrottle {
+    pub fn new(min_delay: Duration) -> Self {
+        Self {
+            min_delay,
+            next_start: Arc::default(),
+        }
+    }
+
+    /// The throttle a batch command's `throttle_hosts` and
+    /// `host_delay_ms` parameters ask for, `None` when it's turned off.
+    pub fn from_options(throttle_hosts: Option<bool>, host_delay_ms: Option<u64>) -> Option<Self> {
+        let delay = host_delay_ms.map_or(DEFAULT_HOST_DELAY, Duration::from_millis);
+        (throttle_hosts.unwrap_or(true) && !delay.is_zero()).then(|| Self::new(delay))
+    }
+
+    /// The key checks are spaced out by: the host of `url` as
+    /// `validate::normalize_url` reads it, so `example.com/a` and
+    /// `https://example.com/b` share one. URLs that don't validate are
+    /// keyed by the whole string; they fail before reaching any host.
+    fn host(url: &str) -> String {
+        validate::normalize_url(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+            .unwrap_or_else(|| url.to_string())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Instant>> {
+        self.next_start.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Claims the start of a check on `host` if its turn has come, moving
+    /// the host's next turn `min_delay` ahead.
+    fn try_claim(&self, host: &str) -> bool {
+        let mut next_start = self.lock();
+        let now = Instant::now();
+        if next_start.get(host).is_some_and(|next| *next > now) {
+            return false;
+        }
+        next_start.insert(host.to_string(), now + self.min_delay);
+        true
+    }
+
+    /// Waits for `host`'s turn and then for a slot in `semaphore`, at least
+    /// `min_delay` after the previous check on the host started. The wait
+    /// for the host happens without a slot, so checks on other hosts keep
+    /// running meanwhile.
+    async fn acquire(&self, host: &str, semaphore: Arc<Semaphore>) -> Result<OwnedSemaphorePermit, AcquireError> {
+        loop {
+            let next = self.lock().get(host).copied();
+            if let Some(next) = next {
+                tokio::time::sleep_until(next).await;
+            }
+            let permit = semaphore.clone().acquire_owned().await?;
+            // Another check on the host may have taken the turn while this
+            // one waited for a slot
+            if self.try_claim(host) {
+                return Ok(permit);
+            }
+        }
+    }
+}
+
+/// How many CLI processes a batch runs at once when the caller doesn't say:
+/// one per core, within `MIN_CONCURRENT..=MAX_CONCURRENT`.
+pub fn default_max_concurrent() -> usize {
+    std::thread::available_parallelism()
+        .map_or(MIN_CONCURRENT, |cores| cores.get())
+        .clamp(MIN_CONCURRENT, MAX_CONCURRENT)
+}
+
+/// Reads the URL to throttle by off a `run_bounded` item.
+type UrlOf<I> = fn(&I) -> &str;
+
+/// Runs `task` for every item, at most `max_concurrent` at a time, returning
+/// the outputs in item order. With a `throttle`, items on the same host (by
+/// the URL the given function returns) start its `min_delay` apart. A task
+/// that panics yields an `Err` for its item.
+async fn run_bounded<I, F, Fut>(
+    items: impl IntoIterator<Item = I>,
+    max_concurrent: usize,
+    throttle: Option<(HostThrottle, UrlOf<I>)>,
+    task: F,
+) -> Vec<Result<Fut::Output, JoinError>>
+where
+    F: Fn(I) -> Fut,
+    Fut: Future + Send + 'static,
+    Fut::Output: Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(max_concurrent.max(1)));
+
+    let handles: Vec<_> = items
+        .into_iter()
+        .map(|item| {
+            let semaphore = semaphore.clone();
+            let host = throttle
+                .as_ref()
+                .map(|(throttle, url)| (throttle.clone(), HostThrottle::host(url(&item))));
+            let run = task(item);
+            tokio::spawn(async move {
+                // The semaphore is never closed, so acquiring only waits
+                let _permit = match host {
+                    Some((throttle, host)) => throttle.acquire(&host, semaphore).await,
+                    None => semaphore.acquire_owned().await,
+                };
+                run.await
+            })
+        })
+        .collect();
+
+    let mut outputs = Vec::with_capacity(handles.len());
+    for handle in handles {
+        outputs.push(handle.await);
+    }
+    outputs
+}
+
+/// Payload of the `batch-item-complete` event, sent as each check of a
+/// batch finishes. Checks finish in any order; `index` is the URL's
+/// position in the batch.
+#[derive(Debug, Clone, Serialize)]
+struct BatchItemEvent<'a> {
+    url: &'a str,
+    result: &'a SeoCheckResult,
+    index: usize,
+    total: usize,
+}
+
+fn emit_item(app: &AppHandle, url: &str, result: &SeoCheckResult, index: usize, total: usize) {
+    let _ = app.emit(
+        "batch-item-complete",
+        BatchItemEvent {
+            url,
+            result,
+            index,
+            total,
+        },
+    );
+}
+
+/// The result for a check that couldn't run at all.
+pub fn failed(error: String) -> SeoCheckResult {
+    SeoCheckResult {
+        success: false,
+        error: Some(error),
+        ..SeoCheckResult::default()
+    }
+}
+
+fn request_url((_, request): &(usize, CheckRequest)) -> &str {
+    &request.url
+}
+
+/// Runs `execute_check` for every request, at most `max_concurrent` at a
+/// time, returning results in request order and emitting each one as a
+/// `batch-item-complete` event as soon as it is ready.
+///
+/// A failing check becomes an unsuccessful result for its URL rather than
+/// failing the batch. With a `throttle`, checks on the same host wait for
+/// their turn before taking a slot. Checks that haven't started when
+/// `cancel_all_checks` is called are cancelled too.
+pub async fn run_batch(
+    app: &AppHandle,
+    requests: Vec<CheckRequest>,
+    max_concurrent: usize,
+    throttle: Option<HostThrottle>,
+) -> Vec<(String, SeoCheckResult)> {
+    let urls: Vec<String> = requests.iter().map(|request| request.url.clone()).collect();
+    let total = urls.len();
+    let generation = app.state::<ProcessRegistry>().generation();
+    let throttle = throttle.map(|throttle| (throttle, request_url as UrlOf<_>));
+    let outputs = run_bounded(requests.into_iter().enumerate(), max_concurrent, throttle, |(index, request)| {
+        let app = app.clone();
+        async move {
+            let url = request.url.clone();
+            let result = if app.state::<ProcessRegistry>().generation() != generation {
+                SeoCheckResult::localized_failure(&app, SeoError::Cancelled)
+            } else {
+                execute_check(&app, request).await.unwrap_or_else(failed)
+            };
+            emit_item(&app, &url, &result, index, total);
+            result
+        }
+    })
+    .await;
+
+    let mut results = Vec::with_capacity(outputs.len());
+    for (index, (url, output)) in urls.into_iter().zip(outputs).enumerate() {
+        let result = output.unwrap_or_else(|e| {
+            let result = failed(format!("SEO check task failed: {}", e));
+            emit_item(app, &url, &result, index, total);
+            result
+        });
+        results.push((url, result));
+    }
+    results
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn run_seo_checks(
+    app: AppHandle,
+    urls: Vec<String>,
+    config: Option<serde_json::Value>,
+    max_concurrent: Option<usize>,
+    webhook_url: Option<String>,
+    webhook_format: Option<WebhookFormat>,
+    throttle_hosts: Option<bool>,
+    host_delay_ms: Option<u64>,
+) -> Result<Vec<(String, SeoCheckResult)>, String> {
+    let webhook = webhook_url
+        .map(|webhook_url| Webhook::new(&webhook_url, webhook_format.unwrap_or_default()))
+        .transpose()?;
+
+    let requests = urls
+        .into_iter()
+        .map(|url| CheckRequest {
+            url,
+            config: config.clone(),
+            ..CheckRequest::default()
+        })
+        .collect();
+
+    let results = run_batch(
+        &app,
+        requests,
+        max_concurrent.unwrap_or_else(default_max_concurrent),
+        HostThrottle::from_options(throttle_hosts, host_delay_ms),
+    )
+    .await;
+    if let Some(webhook) = webhook {
+        webhook.send_batch(&results).await;
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn default_concurrency_is_clamped() {
+        assert!((MIN_CONCURRENT..=MAX_CONCURRENT).contains(&default_max_concurrent()));
+    }
+
+    /// Stands in for the CLI: each "check" just sleeps.
+    async fn mock_check(item: usize) -> usize {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        item
+    }
+
+    #[tokio::test]
+    async fn concurrent_batches_beat_serial_runs() {
+        let items = 0..8;
+
+        let started = Instant::now();
+        let serial = run_bounded(items.clone(), 1, None, mock_check).await;
+        let serial_time = started.elapsed();
+
+        let started = Instant::now();
+        let concurrent = run_bounded(items.clone(), 4, None, mock_check).await;
+        let concurrent_time = started.elapsed();
+
+        let outputs = |results: Vec<Result<usize, JoinError>>| {
+            results.into_iter().map(Result::unwrap).collect::<Vec<_>>()
+        };
+        assert_eq!(outputs(concurrent), items.clone().collect::<Vec<_>>());
+        assert_eq!(outputs(serial), items.collect::<Vec<_>>());
+
+        // 8 checks, 4 at a time: two rounds instead of eight
+        assert!(concurrent_time >= Duration::from_millis(200), "{:?}", concurrent_time);
+        assert!(concurrent_time * 2 < serial_time, "{:?} vs {:?}", concurrent_time, serial_time);
+    }
+
+    fn url<'a>(url: &&'a str) -> &'a str {
+        url
+    }
+
+    #[tokio::test]
+    async fn throttles_checks_per_host() {
+        let throttle = HostThrottle::new(Duration::from_millis(100));
+        let urls = [
+            "https://example.com/a",
+            "EXAMPLE.com/b",
+            "https://example.org/",
+            "example.com",
+            "localhost:3000/x",
+            "http://localhost:3000/y",
+        ];
+
+        let started = Instant::now();
+        let outputs = run_bounded(urls, 6, Some((throttle, url as UrlOf<_>)), |_| async move {
+            started.elapsed()
+        })
+        .await;
+        let starts: Vec<Duration> = outputs.into_iter().map(Result::unwrap).collect();
+
+        // example.com waits its turn three times, localhost twice;
+        // example.org doesn't wait
+        let mut same_host = [starts[0], starts[1], starts[3]];
+        same_host.sort();
+        assert!(same_host[1] >= Duration::from_millis(100), "{:?}", starts);
+        assert!(same_host[2] >= Duration::from_millis(200), "{:?}", starts);
+        assert!(starts[4].abs_diff(starts[5]) >= Duration::from_millis(100), "{:?}", starts);
+        assert!(starts[2] < Duration::from_millis(100), "{:?}", starts);
+
+        assert!(HostThrottle::from_options(Some(false), None).is_none());
+        assert!(HostThrottle::from_options(None, Some(0)).is_none());
+        assert!(HostThrottle::from_options(None, None).is_some());
+    }
+
+    #[tokio::test]
+    async fn throttled_checks_wait_without_a_slot() {
+        let throttle = HostThrottle::new(Duration::from_millis(200));
+        let urls = ["https://example.com/a", "https://example.com/b", "https://example.org/"];
+
+        let started = Instant::now();
+        let outputs = run_bounded(urls, 1, Some((throttle, url as UrlOf<_>)), |_| async move {
+            started.elapsed()
+        })
+        .await;
+        let starts: Vec<Duration> = outputs.into_iter().map(Result::unwrap).collect();
+
+        // example.org takes the only slot while example.com/b waits its turn
+        assert!(starts[1] >= Duration::from_millis(200), "{:?}", starts);
+        assert!(starts[2] < Duration::from_millis(100), "{:?}", starts);
+    }
+}
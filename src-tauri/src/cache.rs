@@ -0,0 +1,71 @@
+use crate::SeoCheckResult;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+use tauri::State;
+
+/// How long a cached result is reused when the caller doesn't say.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Upper bound on cached results; reports are large and batches can be long.
+const MAX_ENTRIES: usize = 256;
+
+type CacheKey = (String, Option<String>);
+
+/// Recent successful check results keyed by normalized URL and config, so
+/// repeating a check doesn't fork the CLI again.
+#[derive(Default)]
+pub struct ResultCache {
+    entries: Mutex<HashMap<CacheKey, (Instant, SeoCheckResult)>>,
+}
+
+impl ResultCache {
+    fn lock(&self) -> MutexGuard<'_, HashMap<CacheKey, (Instant, SeoCheckResult)>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the cached result if it is younger than `ttl`.
+    pub fn get(&self, url: &str, config: Option<&str>, ttl: Duration) -> Option<SeoCheckResult> {
+        let key = (url.to_string(), config.map(str::to_string));
+        let mut entries = self.lock();
+        match entries.get(&key) {
+            Some((stored_at, result)) if stored_at.elapsed() < ttl => Some(result.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn insert(&self, url: &str, config: Option<&str>, result: SeoCheckResult) {
+        let mut entries = self.lock();
+        if entries.len() >= MAX_ENTRIES {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, (stored_at, _))| *stored_at)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = oldest {
+                entries.remove(&key);
+            }
+        }
+        entries.insert(
+            (url.to_string(), config.map(str::to_string)),
+            (Instant::now(), result),
+        );
+    }
+
+    /// Drops every cached result, returning how many there were.
+    pub fn clear(&self) -> usize {
+        let mut entries = self.lock();
+        let count = entries.len();
+        entries.clear();
+        count
+    }
+}
+
+#[tauri::command]
+pub async fn clear_seo_cache(cache: State<'_, ResultCache>) -> Result<(), String> {
+    cache.clear();
+    Ok(())
+}
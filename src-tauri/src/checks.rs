@@ -0,0 +1,119 @@
+use crate::config;
+use crate::presets::{self, PresetStore};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::State;
+
+/// Display name and UI group of every checker, in `presets::ALL_CHECKS` order.
+const CHECKERS: &[(&str, &str, &str)] = &[
+    ("metaTags", "Meta tags", "On-page"),
+    ("headings", "Headings", "On-page"),
+    ("images", "Images", "Media"),
+    ("performance", "Performance", "Performance"),
+    ("robotsTxt", "robots.txt", "Crawling"),
+    ("sitemap", "Sitemap", "Crawling"),
+    ("security", "Security", "Technical"),
+    ("structuredData", "Structured data", "Structured data"),
+    ("socialMedia", "Social media", "On-page"),
+    ("content", "Content", "Content"),
+    ("links", "Links", "Content"),
+    ("uiElements", "UI elements", "User experience"),
+    ("technical", "Technical", "Technical"),
+    ("accessibility", "Accessibility", "User experience"),
+    ("urlFactors", "URL factors", "Technical"),
+    ("spamDetection", "Spam detection", "Content"),
+    ("pageQuality", "Page quality", "Content"),
+    ("advancedImages", "Advanced images", "Media"),
+    ("multimedia", "Multimedia", "Media"),
+    ("coreWebVitals", "Core Web Vitals", "Performance"),
+    ("analytics", "Analytics", "Technical"),
+    ("mobileUX", "Mobile UX", "User experience"),
+    ("schemaValidation", "Schema validation", "Structured data"),
+    ("resourceOptimization", "Resource optimization", "Performance"),
+    ("legalCompliance", "Legal compliance", "Business"),
+    ("ecommerce", "E-commerce", "Business"),
+    ("internationalization", "Internationalization", "Technical"),
+    ("heatmap", "Heatmap", "User experience"),
+];
+
+/// A checker and whether a given config runs it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckDescription {
+    /// The checker's key in reports and configs, e.g. `metaTags`.
+    pub id: String,
+    pub name: String,
+    /// Group for the UI, e.g. `On-page` or `Performance`.
+    pub category: String,
+    pub enabled: bool,
+}
+
+pub fn describe(config: &Value) -> Vec<CheckDescription> {
+    CHECKERS
+        .iter()
+        .map(|(id, name, category)| CheckDescription {
+            id: id.to_string(),
+            name: name.to_string(),
+            category: category.to_string(),
+            enabled: presets::checker_enabled(config, id),
+        })
+        .collect()
+}
+
+/// Lists every checker and whether `config` runs it, without running the
+/// CLI. `config` is a built-in or saved preset id, inline JSON, or a path to
+/// a config file; without one every checker runs.
+#[tauri::command]
+pub async fn describe_checks(
+    config: Option<String>,
+    presets: State<'_, PresetStore>,
+) -> Result<Vec<CheckDescription>, String> {
+    let config = match config.as_deref().map(str::trim) {
+        None | Some("") => Value::Null,
+        Some(id) if presets::builtin_presets().iter().any(|preset| preset.id == id) => {
+            json!({ "preset": id })
+        }
+        Some(config) => match presets.list().into_iter().find(|preset| preset.id == config) {
+            Some(saved) => match saved.config {
+                Some(Value::String(path)) => config::load_config(&path)?,
+                config => config.unwrap_or_default(),
+            },
+            None => config::load_config(config)?,
+        },
+    };
+    Ok(describe(&config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn covers_every_checker_in_report_order() {
+        let ids: Vec<_> = CHECKERS.iter().map(|(id, _, _)| *id).collect();
+        assert_eq!(ids, presets::ALL_CHECKS);
+    }
+
+    #[test]
+    fn follows_the_cli_when_resolving_presets_and_overrides() {
+        let enabled = |config: Value| -> Vec<String> {
+            describe(&config)
+                .into_iter()
+                .filter(|check| check.enabled)
+                .map(|check| check.id)
+                .collect()
+        };
+
+        assert_eq!(enabled(Value::Null).len(), presets::ALL_CHECKS.len());
+        let basic = enabled(json!({ "preset": "basic" }));
+        assert!(basic.contains(&"metaTags".to_string()));
+        assert!(!basic.contains(&"coreWebVitals".to_string()));
+
+        // An object of rules re-enables a checker the preset turned off
+        let custom = enabled(json!({
+            "preset": "basic",
+            "rules": { "coreWebVitals": { "lcp-valid": true }, "metaTags": false }
+        }));
+        assert!(custom.contains(&"coreWebVitals".to_string()));
+        assert!(!custom.contains(&"metaTags".to_string()));
+    }
+}
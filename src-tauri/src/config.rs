@@ -0,0 +1,391 @@
+use crate::error::SeoError;
+use crate::presets;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::ffi::OsStr;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+
+const TOP_LEVEL_KEYS: &[&str] = &["preset", "severity", "rules", "customRules"];
+const PRESET_IDS: &[&str] = &["basic", "advanced", "strict"];
+const SEVERITIES: &[&str] = &["error", "warning", "info"];
+
+/// A check's config in the form the CLI takes it.
+pub enum CliConfig {
+    /// A built-in preset id, passed as `--preset`.
+    Preset(String),
+    /// A config file on disk, passed as `--config`.
+    File(PathBuf),
+    /// An inline config written to a private temp file, which is deleted
+    /// when this value is dropped, whether or not the check succeeded.
+    Inline(NamedTempFile),
+}
+
+impl CliConfig {
+    /// Interprets the `config` argument of a check.
+    ///
+    /// A JSON object is an inline config. A string is a preset id when it
+    /// names a built-in preset, runs the config of the preset in `saved`
+    /// with that id if there is one, and is a file path otherwise.
+    pub fn from_value(config: &Value, saved: &[presets::Preset]) -> Result<Option<Self>, SeoError> {
+        if let Value::String(id) = config {
+            if let Some(preset) = saved.iter().find(|preset| !preset.builtin && preset.id == *id) {
+                return Self::parse(preset.config.as_ref().unwrap_or(&Value::Null));
+            }
+        }
+        Self::parse(config)
+    }
+
+    fn parse(config: &Value) -> Result<Option<Self>, SeoError> {
+        match config {
+            Value::Null => Ok(None),
+            Value::String(s) if PRESET_IDS.contains(&s.as_str()) => Ok(Some(Self::Preset(s.clone()))),
+            // Would be read as a CLI flag; `./-name.json` still works
+            Value::String(s) if s.starts_with('-') => Err(SeoError::InvalidConfig {
+                message: format!("config path must not start with '-' ({})", s),
+            }),
+            Value::String(s) => Ok(Some(Self::File(PathBuf::from(s)))),
+            Value::Object(_) => Self::write_inline(config).map(Some),
+            _ => Err(SeoError::InvalidConfig {
+                message: "expected a file path, a preset id, or a JSON object".to_string(),
+            }),
+        }
+    }
+
+    fn write_inline(config: &Value) -> Result<Self, SeoError> {
+        let to_error = |e: std::io::Error| SeoError::InvalidConfig {
+            message: format!("failed to write temporary config file: {}", e),
+        };
+
+        // The CLI picks its parser from the extension
+        let mut file = tempfile::Builder::new()
+            .prefix("e2e-seo-config-")
+            .suffix(".json")
+            .tempfile()
+            .map_err(to_error)?;
+        serde_json::to_writer(&mut file, config)
+            .map_err(|e| to_error(e.into()))?;
+        file.flush().map_err(to_error)?;
+
+        Ok(Self::Inline(file))
+    }
+
+    /// The CLI flag and value selecting this config.
+    pub fn args(&self) -> [&OsStr; 2] {
+        match self {
+            Self::Preset(id) => ["--preset".as_ref(), id.as_ref()],
+            Self::File(path) => ["--config".as_ref(), path.as_os_str()],
+            Self::Inline(file) => ["--config".as_ref(), file.path().as_os_str()],
+        }
+    }
+}
+
+/// A stable string form of a check's config, for cache keys and history.
+pub fn config_key(config: &Value) -> Option<String> {
+    match config {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        // Object keys are sorted, so equal configs serialize the same
+        other => Some(other.to_string()),
+    }
+}
+
+/// The form of a check's config the result cache is keyed on. Unlike
+/// `config_key`, it changes whenever the config does: a saved preset is
+/// keyed on the config it runs, and a config file on its canonical path,
+/// modification time and size.
+pub fn cache_key(config: &Value, saved: &[presets::Preset]) -> Option<String> {
+    let Value::String(id) = config else {
+        return config_key(config);
+    };
+    if PRESET_IDS.contains(&id.as_str()) {
+        return Some(id.clone());
+    }
+    if let Some(preset) = saved.iter().find(|preset| !preset.builtin && preset.id == *id) {
+        // Saved presets hold an object or a path, never another preset's id
+        return cache_key(preset.config.as_ref().unwrap_or(&Value::Null), &[]);
+    }
+
+    let path = Path::new(id);
+    let file = std::fs::canonicalize(path).and_then(|path| Ok((path.metadata()?, path)));
+    match file {
+        Ok((metadata, path)) => {
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or(0, |modified| modified.as_nanos());
+            Some(format!("{}@{}:{}", path.display(), modified, metadata.len()))
+        }
+        // The check fails on it anyway, so there's nothing to cache
+        Err(_) => Some(id.clone()),
+    }
+}
+
+/// What `validate_config` found in a config, mirroring `SEOConfig` in
+/// `src/config/types.ts`. Keys are reported as dotted paths.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigValidation {
+    /// True when there are no type errors; unknown keys are only warnings
+    /// because the CLI ignores them.
+    pub valid: bool,
+    pub recognized_keys: Vec<String>,
+    pub unknown_keys: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// Reads a config given either as inline JSON or as a path to a `.json`,
+/// `.yaml` or `.yml` file. Input that parses as a JSON object is inline.
+pub fn load_config(config: &str) -> Result<Value, String> {
+    if let Ok(value @ Value::Object(_)) = serde_json::from_str::<Value>(config) {
+        return Ok(value);
+    }
+
+    let path = Path::new(config.trim());
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        format!("Config is neither inline JSON nor a readable file ({}): {}", path.display(), e)
+    })?;
+
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("json") => serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e)),
+        Some("yaml" | "yml") => serde_yaml::from_str(&content)
+            .map_err(|e| format!("Invalid YAML in {}: {}", path.display(), e)),
+        _ => Err(format!(
+            "Unsupported configuration file format: {}. Use .json, .yaml, or .yml",
+            path.display()
+        )),
+    }
+}
+
+pub fn validate(value: &Value) -> ConfigValidation {
+    let mut report = ConfigValidation::default();
+
+    let Some(root) = value.as_object() else {
+        report.errors.push("config: expected an object".to_string());
+        return report;
+    };
+
+    for (key, value) in root {
+        if !TOP_LEVEL_KEYS.contains(&key.as_str()) {
+            report.unknown_keys.push(key.clone());
+            continue;
+        }
+        report.recognized_keys.push(key.clone());
+
+        match key.as_str() {
+            "preset" => check_enum(&mut report, key, value, PRESET_IDS),
+            "severity" => check_enum(&mut report, key, value, SEVERITIES),
+            "rules" => validate_rules(&mut report, value),
+            "customRules" => match value.as_object() {
+                Some(rules) => {
+                    for (name, rule) in rules {
+                        validate_rule(&mut report, &format!("customRules.{}", name), rule);
+                    }
+                }
+                None => report.errors.push("customRules: expected an object".to_string()),
+            },
+            _ => unreachable!("all top-level keys are handled"),
+        }
+    }
+
+    report.valid = report.errors.is_empty();
+    report
+}
+
+fn validate_rules(report: &mut ConfigValidation, value: &Value) {
+    let Some(checkers) = value.as_object() else {
+        report.errors.push("rules: expected an object".to_string());
+        return;
+    };
+
+    for (checker, rules) in checkers {
+        let path = format!("rules.{}", checker);
+        if !presets::ALL_CHECKS.contains(&checker.as_str()) {
+            report.unknown_keys.push(path);
+            continue;
+        }
+        report.recognized_keys.push(path.clone());
+
+        match rules {
+            Value::Bool(_) => {}
+            Value::Object(rules) => validate_checker_rules(report, &path, rules),
+            _ => report
+                .errors
+                .push(format!("{}: expected a boolean or an object of rules", path)),
+        }
+    }
+}
+
+fn validate_checker_rules(report: &mut ConfigValidation, path: &str, rules: &Map<String, Value>) {
+    for (name, rule) in rules {
+        let rule_path = format!("{}.{}", path, name);
+        match name.as_str() {
+            // Checker-wide settings, as used by the built-in presets
+            "enabled" => check_bool(report, &rule_path, rule),
+            "severity" => check_enum(report, &rule_path, rule, SEVERITIES),
+            _ => validate_rule(report, &rule_path, rule),
+        }
+    }
+}
+
+/// Validates a single rule: `true`/`false` or `{ enabled, severity?, options? }`.
+fn validate_rule(report: &mut ConfigValidation, path: &str, rule: &Value) {
+    let rule = match rule {
+        Value::Bool(_) => return,
+        Value::Object(rule) => rule,
+        _ => {
+            report
+                .errors
+                .push(format!("{}: expected a boolean or a rule object", path));
+            return;
+        }
+    };
+
+    for (key, value) in rule {
+        let key_path = format!("{}.{}", path, key);
+        match key.as_str() {
+            "enabled" => check_bool(report, &key_path, value),
+            "severity" => check_enum(report, &key_path, value, SEVERITIES),
+            "options" => {
+                if !value.is_object() {
+                    report.errors.push(format!("{}: expected an object", key_path));
+                }
+            }
+            _ => report.unknown_keys.push(key_path),
+        }
+    }
+}
+
+fn check_bool(report: &mut ConfigValidation, path: &str, value: &Value) {
+    if !value.is_boolean() {
+        report.errors.push(format!("{}: expected a boolean", path));
+    }
+}
+
+fn check_enum(report: &mut ConfigValidation, path: &str, value: &Value, allowed: &[&str]) {
+    match value.as_str() {
+        Some(s) if allowed.contains(&s) => {}
+        _ => report.errors.push(format!("{}: expected one of {}", path, allowed.join(", "))),
+    }
+}
+
+#[tauri::command]
+pub async fn validate_config(config: String) -> Result<ConfigValidation, String> {
+    Ok(validate(&load_config(&config)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn accepts_preset_style_config() {
+        let report = validate(&json!({
+            "preset": "advanced",
+            "severity": "warning",
+            "rules": {
+                "metaTags": { "title-exists": { "enabled": true, "severity": "error" } },
+                "coreWebVitals": { "enabled": true, "severity": "warning" },
+                "analytics": false
+            }
+        }));
+        assert!(report.valid, "{:?}", report.errors);
+        assert!(report.unknown_keys.is_empty());
+        assert!(report.recognized_keys.contains(&"rules.coreWebVitals".to_string()));
+    }
+
+    #[test]
+    fn reports_unknown_keys_and_type_errors() {
+        let report = validate(&json!({
+            "preset": "extreme",
+            "theme": "dark",
+            "rules": {
+                "metaTag": true,
+                "images": "yes",
+                "headings": { "h1-exists": { "enabled": "true", "colour": 1 } }
+            }
+        }));
+        assert!(!report.valid);
+        assert_eq!(
+            report.unknown_keys,
+            vec!["rules.headings.h1-exists.colour", "rules.metaTag", "theme"]
+        );
+        assert_eq!(report.errors.len(), 3);
+    }
+
+    #[test]
+    fn maps_config_values_to_cli_args() {
+        assert!(CliConfig::from_value(&Value::Null, &[]).unwrap().is_none());
+
+        let preset = CliConfig::from_value(&json!("strict"), &[]).unwrap().unwrap();
+        assert_eq!(preset.args(), ["--preset", "strict"].map(OsStr::new));
+
+        let file = CliConfig::from_value(&json!("configs/site.yaml"), &[]).unwrap().unwrap();
+        assert_eq!(file.args(), ["--config", "configs/site.yaml"].map(OsStr::new));
+
+        assert!(CliConfig::from_value(&json!(42), &[]).is_err());
+    }
+
+    #[test]
+    fn cache_keys_change_with_the_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("site.json");
+        std::fs::write(&file, r#"{ "preset": "basic" }"#).unwrap();
+        let path = json!(file.to_str().unwrap());
+        let before = cache_key(&path, &[]).unwrap();
+        std::fs::write(&file, r#"{ "preset": "advanced" }"#).unwrap();
+        assert_ne!(cache_key(&path, &[]).unwrap(), before);
+
+        let store = presets::PresetStore::open(dir.path()).unwrap();
+        let saved = |config| {
+            store.save_preset("Marketing", config).unwrap();
+            cache_key(&json!("Marketing"), &store.list()).unwrap()
+        };
+        assert_ne!(saved(json!({ "preset": "basic" })), saved(json!({ "preset": "strict" })));
+        assert_eq!(cache_key(&json!("strict"), &store.list()).as_deref(), Some("strict"));
+        assert!(cache_key(&Value::Null, &[]).is_none());
+    }
+
+    #[test]
+    fn runs_saved_presets_by_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = presets::PresetStore::open(dir.path()).unwrap();
+        let config = json!({ "preset": "basic", "rules": { "images": false } });
+        store.save_preset("Marketing", config.clone()).unwrap();
+
+        let saved = CliConfig::from_value(&json!("Marketing"), &store.list()).unwrap().unwrap();
+        assert_eq!(saved.args()[0], "--config");
+        let written: Value = serde_json::from_str(&std::fs::read_to_string(saved.args()[1]).unwrap()).unwrap();
+        assert_eq!(written, config);
+
+        // Built-in ids still select the CLI's preset
+        let builtin = CliConfig::from_value(&json!("basic"), &store.list()).unwrap().unwrap();
+        assert_eq!(builtin.args(), ["--preset", "basic"].map(OsStr::new));
+    }
+
+    #[test]
+    fn inline_config_file_is_removed_on_drop() {
+        let config = json!({ "preset": "basic", "rules": { "images": false } });
+        let inline = CliConfig::from_value(&config, &[]).unwrap().unwrap();
+        let path = PathBuf::from(inline.args()[1]);
+
+        let written: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written, config);
+
+        drop(inline);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn loads_inline_json() {
+        assert_eq!(load_config(r#"{"preset":"basic"}"#).unwrap(), json!({ "preset": "basic" }));
+        assert!(load_config("/nonexistent/config.json").is_err());
+    }
+}
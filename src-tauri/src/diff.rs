@@ -0,0 +1,205 @@
+use crate::report::{CheckResult, Score, SeoReport};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+/// One check as it appeared in each of two reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckDiff {
+    /// Stable identifier of the check within its category, e.g. `metaTags[0]`.
+    pub id: String,
+    pub category: String,
+    /// `None` when the check only appears in the other report.
+    pub before: Option<CheckResult>,
+    pub after: Option<CheckResult>,
+}
+
+impl CheckDiff {
+    /// A check missing from a report was not failing there.
+    fn passed_before(&self) -> bool {
+        self.before.as_ref().map_or(true, |check| check.passed)
+    }
+
+    fn passed_after(&self) -> bool {
+        self.after.as_ref().map_or(true, |check| check.passed)
+    }
+}
+
+/// What changed between two audits of a page.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportDiff {
+    pub score_before: Option<f64>,
+    pub score_after: Option<f64>,
+    /// `score_after - score_before`, when both reports have a score.
+    pub score_delta: Option<f64>,
+    /// Failing before, passing (or gone) after.
+    pub fixed: Vec<CheckDiff>,
+    /// Passing (or absent) before, failing after.
+    pub new_issues: Vec<CheckDiff>,
+    pub unchanged: Vec<CheckDiff>,
+}
+
+/// Compares two reports check by check.
+///
+/// Checkers report their checks in a fixed order, so checks are matched by
+/// position within their category and a reworded message (such as a new
+/// character count) is still the same check. When a category has a different
+/// number of checks in each report, positions can't be trusted and checks
+/// are matched by message with numbers ignored instead.
+pub fn diff(before: &SeoReport, after: &SeoReport) -> ReportDiff {
+    let mut report = ReportDiff {
+        score_before: before.score.map(Score::percent),
+        score_after: after.score.map(Score::percent),
+        score_delta: before.score.zip(after.score).map(|(b, a)| a.percent() - b.percent()),
+        ..ReportDiff::default()
+    };
+
+    let categories: BTreeSet<&String> = before.checks.keys().chain(after.checks.keys()).collect();
+    for category in categories {
+        let empty = Vec::new();
+        let old = before.checks.get(category).unwrap_or(&empty);
+        let new = after.checks.get(category).unwrap_or(&empty);
+
+        // A category only one report has can't have been reordered
+        let pairs = if old.len() == new.len() || old.is_empty() || new.is_empty() {
+            match_by_position(category, old, new)
+        } else {
+            match_by_message(category, old, new)
+        };
+
+        for pair in pairs {
+            match (pair.passed_before(), pair.passed_after()) {
+                (false, true) => report.fixed.push(pair),
+                (true, false) => report.new_issues.push(pair),
+                _ => report.unchanged.push(pair),
+            }
+        }
+    }
+    report
+}
+
+fn match_by_position(category: &str, old: &[CheckResult], new: &[CheckResult]) -> Vec<CheckDiff> {
+    (0..old.len().max(new.len()))
+        .map(|index| CheckDiff {
+            id: format!("{}[{}]", category, index),
+            category: category.to_string(),
+            before: old.get(index).cloned(),
+            after: new.get(index).cloned(),
+        })
+        .collect()
+}
+
+fn match_by_message(category: &str, old: &[CheckResult], new: &[CheckResult]) -> Vec<CheckDiff> {
+    let keys: Vec<String> = old.iter().map(|check| message_key(&check.message)).collect();
+
+    // Old checks with the same key pair up with new ones in order
+    let mut unmatched: HashMap<&str, VecDeque<usize>> = HashMap::new();
+    for (index, key) in keys.iter().enumerate() {
+        unmatched.entry(key).or_default().push_back(index);
+    }
+
+    let mut pairs: Vec<CheckDiff> = new
+        .iter()
+        .map(|after| {
+            let key = message_key(&after.message);
+            let before = unmatched.get_mut(key.as_str()).and_then(VecDeque::pop_front);
+            CheckDiff {
+                id: format!("{}:{}", category, key),
+                category: category.to_string(),
+                before: before.map(|index| old[index].clone()),
+                after: Some(after.clone()),
+            }
+        })
+        .collect();
+
+    // Whatever is left only exists in the old report
+    let mut gone: Vec<usize> = unmatched.into_values().flatten().collect();
+    gone.sort_unstable();
+    pairs.extend(gone.into_iter().map(|index| CheckDiff {
+        id: format!("{}:{}", category, keys[index]),
+        category: category.to_string(),
+        before: Some(old[index].clone()),
+        after: None,
+    }));
+    pairs
+}
+
+/// A check's message in lowercase with each run of digits replaced by `#`,
+/// so counts and measurements don't make a check look new.
+pub fn message_key(message: &str) -> String {
+    let mut key = String::with_capacity(message.len());
+    for c in message.trim().chars() {
+        if c.is_ascii_digit() {
+            if !key.ends_with('#') {
+                key.push('#');
+            }
+        } else {
+            key.extend(c.to_lowercase());
+        }
+    }
+    key
+}
+
+#[tauri::command]
+pub fn diff_reports(before: SeoReport, after: SeoReport) -> Result<ReportDiff, String> {
+    Ok(diff(&before, &after))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn ids(checks: &[CheckDiff]) -> Vec<&str> {
+        checks.iter().map(|check| check.id.as_str()).collect()
+    }
+
+    #[test]
+    fn matches_checks_by_position_despite_rewording() {
+        let before = SeoReport::from_value(json!({
+            "score": 60,
+            "checks": { "metaTags": [
+                { "passed": false, "message": "Title is too short (12 characters)" },
+                { "passed": true, "message": "Meta description is optimal" }
+            ] }
+        }))
+        .unwrap();
+        let after = SeoReport::from_value(json!({
+            "score": 75.5,
+            "checks": { "metaTags": [
+                { "passed": true, "message": "Title is optimal (42 characters)" },
+                { "passed": false, "message": "Meta description is missing" }
+            ] }
+        }))
+        .unwrap();
+
+        let diff = diff(&before, &after);
+        assert_eq!(diff.score_delta, Some(15.5));
+        assert_eq!(ids(&diff.fixed), vec!["metaTags[0]"]);
+        assert_eq!(ids(&diff.new_issues), vec!["metaTags[1]"]);
+        assert!(diff.unchanged.is_empty());
+    }
+
+    #[test]
+    fn falls_back_to_messages_when_counts_differ() {
+        let before = SeoReport::from_value(json!({
+            "checks": { "links": [
+                { "passed": false, "message": "Found 3 broken links" },
+                { "passed": false, "message": "Link to /old is broken" }
+            ] }
+        }))
+        .unwrap();
+        let after = SeoReport::from_value(json!({
+            "checks": { "links": [
+                { "passed": false, "message": "Found 1 broken links" }
+            ], "images": [{ "passed": false, "message": "Image missing alt text" }] }
+        }))
+        .unwrap();
+
+        let diff = diff(&before, &after);
+        assert_eq!(diff.score_delta, None);
+        assert_eq!(ids(&diff.unchanged), vec!["links:found # broken links"]);
+        assert_eq!(ids(&diff.fixed), vec!["links:link to /old is broken"]);
+        assert_eq!(ids(&diff.new_issues), vec!["images[0]"]);
+        assert!(diff.fixed[0].after.is_none());
+    }
+}
@@ -0,0 +1,218 @@
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use tauri::{AppHandle, Manager, State};
+use tokio::process::Command;
+
+/// Oldest Node.js release the CLI's Playwright dependency supports.
+pub const MIN_NODE_MAJOR: u64 = 18;
+
+/// Where to send users whose Node.js is missing or too old.
+pub const NODE_INSTALL_HINT: &str =
+    "Install Node.js 18 or newer from https://nodejs.org/ and restart the app";
+
+/// Whether this machine can run the SEO checker CLI with the system `node`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentStatus {
+    /// False when checks run through the bundled sidecar, which needs no Node.js.
+    pub node_required: bool,
+    pub node_installed: bool,
+    /// The `node` executable checks run: the one set with `set_node_path`,
+    /// or the first on `PATH`. `None` when there is none on `PATH`.
+    pub node_path: Option<String>,
+    /// The version reported by `node --version`, without the leading `v`.
+    pub node_version: Option<String>,
+    pub minimum_version: String,
+    pub meets_minimum: bool,
+    /// Setup instructions when Node.js is required but unusable.
+    pub message: Option<String>,
+}
+
+/// The most recent environment probe, taken at startup and refreshed by
+/// `check_environment`.
+#[derive(Default)]
+pub struct EnvironmentCache {
+    status: Mutex<Option<EnvironmentStatus>>,
+}
+
+impl EnvironmentCache {
+    pub fn get(&self) -> Option<EnvironmentStatus> {
+        self.status
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    fn set(&self, status: EnvironmentStatus) {
+        *self.status.lock().unwrap_or_else(PoisonError::into_inner) = Some(status);
+    }
+}
+
+/// The `node` executable chosen with `set_node_path`, replacing the one on
+/// `PATH` for users with several installed, e.g. through nvm.
+#[derive(Default)]
+pub struct NodePath {
+    path: Mutex<Option<PathBuf>>,
+}
+
+impl NodePath {
+    fn get(&self) -> Option<PathBuf> {
+        self.path.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+}
+
+/// The program to run Node.js with: the `set_node_path` override, else
+/// plain `node` for the OS to look up on `PATH`.
+pub fn node_program(app: &AppHandle) -> PathBuf {
+    app.state::<NodePath>()
+        .get()
+        .unwrap_or_else(|| PathBuf::from("node"))
+}
+
+/// The first `node` executable on `PATH`, as the OS would find it.
+fn find_on_path() -> Option<PathBuf> {
+    let name = if cfg!(windows) { "node.exe" } else { "node" };
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(name))
+        .find(|candidate| is_executable(candidate))
+}
+
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        path.metadata()
+            .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
+}
+
+/// Checks a `set_node_path` override, which must be an executable file.
+fn validate_node_path(path: &str) -> Result<PathBuf, String> {
+    let path = Path::new(path.trim());
+    let path = path
+        .canonicalize()
+        .map_err(|e| format!("Node.js not found at {}: {}", path.display(), e))?;
+    if !is_executable(&path) {
+        return Err(format!("{} is not an executable file", path.display()));
+    }
+    Ok(path)
+}
+
+/// Runs `node --version` and checks it against `MIN_NODE_MAJOR`.
+pub async fn probe(app: &AppHandle) -> EnvironmentStatus {
+    let configured = app.state::<NodePath>().get();
+    let node_path = configured.clone().or_else(find_on_path);
+    let version = Command::new(node_program(app))
+        .arg("--version")
+        .output()
+        .await
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| parse_node_version(&String::from_utf8_lossy(&output.stdout)));
+
+    let meets_minimum = version
+        .as_ref()
+        .is_some_and(|version| version.major >= MIN_NODE_MAJOR);
+    let node_required = crate::cli::uses_system_node();
+
+    let message = match &version {
+        _ if !node_required || meets_minimum => None,
+        Some(version) => Some(format!("Node.js {} is too old. {}", version, NODE_INSTALL_HINT)),
+        None => match &configured {
+            Some(path) => Some(format!(
+                "{} did not report a Node.js version. {}",
+                path.display(),
+                NODE_INSTALL_HINT
+            )),
+            None => Some(format!("Node.js was not found on PATH. {}", NODE_INSTALL_HINT)),
+        },
+    };
+
+    EnvironmentStatus {
+        node_required,
+        node_installed: version.is_some(),
+        node_path: node_path.map(|path| path.display().to_string()),
+        node_version: version.map(|version| version.to_string()),
+        minimum_version: format!("{}.0.0", MIN_NODE_MAJOR),
+        meets_minimum,
+        message,
+    }
+}
+
+fn parse_node_version(output: &str) -> Option<Version> {
+    Version::parse(output.trim().trim_start_matches('v')).ok()
+}
+
+/// Probes the environment in the background so startup isn't delayed.
+pub fn probe_on_startup(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let status = probe(&app).await;
+        if let Some(message) = &status.message {
+            log::warn!("{}", message);
+        }
+        app.state::<EnvironmentCache>().set(status);
+    });
+}
+
+#[tauri::command]
+pub async fn check_environment(
+    app: AppHandle,
+    cache: State<'_, EnvironmentCache>,
+) -> Result<EnvironmentStatus, String> {
+    let status = probe(&app).await;
+    cache.set(status.clone());
+    Ok(status)
+}
+
+/// Sets (or with `None`, clears) the `node` executable and probes it, so
+/// checks fail early with its problems as they would for one on `PATH`.
+#[tauri::command]
+pub async fn set_node_path(
+    app: AppHandle,
+    path: Option<String>,
+    node_path: State<'_, NodePath>,
+    cache: State<'_, EnvironmentCache>,
+) -> Result<EnvironmentStatus, String> {
+    let path = path.as_deref().map(validate_node_path).transpose()?;
+    *node_path.path.lock().unwrap_or_else(PoisonError::into_inner) = path;
+
+    let status = probe(&app).await;
+    cache.set(status.clone());
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_node_version_output() {
+        assert_eq!(parse_node_version("v20.11.1\n"), Some(Version::new(20, 11, 1)));
+        assert_eq!(parse_node_version("18.0.0"), Some(Version::new(18, 0, 0)));
+        assert_eq!(parse_node_version("not a version"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn node_path_must_be_an_executable_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let node = dir.path().join("node");
+        std::fs::write(&node, "#!/bin/sh\necho v20.0.0\n").unwrap();
+        assert!(validate_node_path(node.to_str().unwrap())
+            .unwrap_err()
+            .contains("is not an executable file"));
+
+        std::fs::set_permissions(&node, std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(validate_node_path(node.to_str().unwrap()).unwrap(), node.canonicalize().unwrap());
+        assert!(validate_node_path(dir.path().to_str().unwrap()).is_err());
+        assert!(validate_node_path("/nonexistent/node").unwrap_err().starts_with("Node.js not found"));
+    }
+}
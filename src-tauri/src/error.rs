@@ -0,0 +1,179 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Substrings of CLI error output that point to a network problem worth
+/// retrying, after the CLI's own `categorizeError` in `src/errors/types.ts`.
+const TRANSIENT_MARKERS: &[&str] = &[
+    "ETIMEDOUT",
+    "ECONNREFUSED",
+    "ECONNRESET",
+    "EAI_AGAIN",
+    "ERR_CONNECTION",
+    "ERR_TIMED_OUT",
+    "ERR_NETWORK_CHANGED",
+    "ERR_INTERNET_DISCONNECTED",
+    "fetch failed",
+    "socket hang up",
+];
+
+/// Why an SEO check failed, serialized with a machine-readable `code` so the
+/// frontend can branch on the kind of failure.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum SeoError {
+    /// The CLI (sidecar binary, `node`, or `dist/cli.js`) couldn't be found.
+    CliNotFound { message: String },
+    /// The CLI's version couldn't be determined or is too old for a request.
+    UnsupportedCli { message: String },
+    /// The CLI exists but the process couldn't be started or waited on.
+    SpawnFailed { message: String },
+    /// The CLI ran past its deadline and was killed.
+    Timeout { secs: u64 },
+    /// The check was cancelled with `cancel_seo_check` or by app exit.
+    Cancelled,
+    InvalidUrl { message: String },
+    /// The page didn't answer the pre-flight request, or answered with an
+    /// HTTP error `status`.
+    Unreachable { status: Option<u16>, message: String },
+    /// The config argument couldn't be turned into something the CLI accepts.
+    InvalidConfig { message: String },
+    /// An extra CLI argument, such as a header, was rejected.
+    InvalidArgument { message: String },
+    /// The CLI exited unsuccessfully.
+    CliExitError { exit_code: Option<i32>, stderr: String },
+    /// The CLI wrote more than `limit_bytes` to stdout or stderr and was killed.
+    OutputTooLarge { limit_bytes: usize },
+    /// The CLI succeeded but its output wasn't valid UTF-8; `offset` is the
+    /// byte where the first invalid sequence starts.
+    CliOutputEncoding { offset: usize },
+    /// The CLI succeeded but its output wasn't valid JSON.
+    JsonParseError { message: String },
+    /// The CLI's JSON doesn't match the report schema; `errors` lists each
+    /// offending path and `raw` holds the JSON as printed.
+    SchemaMismatch {
+        errors: Vec<String>,
+        raw: serde_json::Value,
+    },
+}
+
+impl fmt::Display for SeoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeoError::CliNotFound { message } => write!(f, "{}", message),
+            SeoError::UnsupportedCli { message } => write!(f, "{}", message),
+            SeoError::SpawnFailed { message } => {
+                write!(f, "Failed to execute SEO checker: {}", message)
+            }
+            SeoError::Timeout { secs } => write!(f, "SEO check timed out after {} seconds", secs),
+            SeoError::Cancelled => write!(f, "SEO check was cancelled"),
+            SeoError::InvalidUrl { message } => write!(f, "Invalid URL: {}", message),
+            SeoError::Unreachable { message, .. } => write!(f, "Page is unreachable: {}", message),
+            SeoError::InvalidConfig { message } => write!(f, "Invalid config: {}", message),
+            SeoError::InvalidArgument { message } => write!(f, "Invalid argument: {}", message),
+            SeoError::CliExitError { exit_code, stderr } => {
+                if !stderr.trim().is_empty() {
+                    write!(f, "{}", stderr)
+                } else if let Some(code) = exit_code {
+                    write!(f, "SEO checker exited with code {}", code)
+                } else {
+                    write!(f, "SEO checker was terminated by a signal")
+                }
+            }
+            SeoError::OutputTooLarge { limit_bytes } => write!(
+                f,
+                "SEO checker output exceeded {} bytes and the check was stopped",
+                limit_bytes
+            ),
+            SeoError::CliOutputEncoding { offset } => {
+                write!(f, "SEO checker output is not valid UTF-8 (invalid byte at offset {})", offset)
+            }
+            SeoError::JsonParseError { message } => {
+                write!(f, "Failed to parse JSON output: {}", message)
+            }
+            SeoError::SchemaMismatch { errors, .. } => write!(
+                f,
+                "SEO checker output doesn't match the expected report format: {}",
+                errors.join("; ")
+            ),
+        }
+    }
+}
+
+impl SeoError {
+    /// The `code` this error is serialized with.
+    pub fn code(&self) -> &'static str {
+        match self {
+            SeoError::CliNotFound { .. } => "cli_not_found",
+            SeoError::UnsupportedCli { .. } => "unsupported_cli",
+            SeoError::SpawnFailed { .. } => "spawn_failed",
+            SeoError::Timeout { .. } => "timeout",
+            SeoError::Cancelled => "cancelled",
+            SeoError::InvalidUrl { .. } => "invalid_url",
+            SeoError::Unreachable { .. } => "unreachable",
+            SeoError::InvalidConfig { .. } => "invalid_config",
+            SeoError::InvalidArgument { .. } => "invalid_argument",
+            SeoError::CliExitError { .. } => "cli_exit_error",
+            SeoError::OutputTooLarge { .. } => "output_too_large",
+            SeoError::CliOutputEncoding { .. } => "cli_output_encoding",
+            SeoError::JsonParseError { .. } => "json_parse_error",
+            SeoError::SchemaMismatch { .. } => "schema_mismatch",
+        }
+    }
+
+    /// Whether running the same check again might succeed.
+    ///
+    /// Only failures caused by the network or an overloaded server qualify;
+    /// a bad URL or config fails the same way every time, and a timed-out
+    /// check would just wait out its deadline again.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            SeoError::Unreachable { status, .. } => {
+                status.map_or(true, |status| status == 429 || status >= 500)
+            }
+            SeoError::CliExitError { stderr, .. } => {
+                TRANSIENT_MARKERS.iter().any(|marker| stderr.contains(marker))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl std::error::Error for SeoError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_matches_serialized_tag() {
+        let errors = [
+            SeoError::Timeout { secs: 1 },
+            SeoError::Cancelled,
+            SeoError::InvalidArgument { message: String::new() },
+            SeoError::CliExitError { exit_code: None, stderr: String::new() },
+            SeoError::CliOutputEncoding { offset: 0 },
+            SeoError::Unreachable { status: Some(404), message: String::new() },
+        ];
+        for error in errors {
+            assert_eq!(serde_json::to_value(&error).unwrap()["code"], error.code());
+        }
+    }
+
+    #[test]
+    fn only_network_failures_are_transient() {
+        let exit = |stderr: &str| SeoError::CliExitError {
+            exit_code: Some(1),
+            stderr: stderr.to_string(),
+        };
+        assert!(exit("❌ Error: page.goto: net::ERR_CONNECTION_RESET at https://example.com/").is_transient());
+        assert!(exit("❌ Error: connect ECONNREFUSED 127.0.0.1:443").is_transient());
+        assert!(!exit("❌ Error: Unsupported configuration file format: site.toml").is_transient());
+        assert!(!SeoError::Timeout { secs: 60 }.is_transient());
+        assert!(!SeoError::InvalidUrl { message: "ECONNRESET".to_string() }.is_transient());
+
+        let unreachable = |status| SeoError::Unreachable { status, message: String::new() };
+        assert!(unreachable(None).is_transient());
+        assert!(unreachable(Some(503)).is_transient());
+        assert!(!unreachable(Some(404)).is_transient());
+    }
+}
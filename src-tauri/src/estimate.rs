@@ -0,0 +1,86 @@
+use crate::batch::default_max_concurrent;
+use crate::history::HistoryStore;
+use crate::{config, validate};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use tauri::State;
+
+/// Assumed duration of a check when history has none with the same config,
+/// on the slow side: most of a check is launching a browser and loading the
+/// page, and a surprise wait is worse than a pleasant one.
+const DEFAULT_PER_URL_MS: u64 = 30_000;
+
+/// How long a batch of checks is likely to take.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEstimate {
+    /// Wall-clock time for the whole batch, at the given concurrency.
+    pub estimated_secs: u64,
+    pub url_count: usize,
+    /// Average expected duration of one check.
+    pub avg_per_url_ms: u64,
+}
+
+/// Estimates a batch with checks taking `durations_ms`, started in order as
+/// slots free up, at most `max_concurrent` at a time, as `run_batch` does.
+fn estimate(durations_ms: &[u64], max_concurrent: usize) -> AuditEstimate {
+    let mut slots: BinaryHeap<Reverse<u64>> = (0..max_concurrent.max(1)).map(|_| Reverse(0)).collect();
+    let mut finished_ms = 0;
+    for duration in durations_ms {
+        let Reverse(free_at) = slots.pop().unwrap_or(Reverse(0));
+        finished_ms = finished_ms.max(free_at + duration);
+        slots.push(Reverse(free_at + duration));
+    }
+
+    let total_ms: u64 = durations_ms.iter().sum();
+    AuditEstimate {
+        estimated_secs: finished_ms.div_ceil(1000),
+        url_count: durations_ms.len(),
+        avg_per_url_ms: total_ms.checked_div(durations_ms.len() as u64).unwrap_or(0),
+    }
+}
+
+/// Estimates how long `run_seo_checks` would take for `urls`. Each URL is
+/// expected to take as long as its recent checks with `config` did, or
+/// the average recent check with `config` if it has none.
+#[tauri::command]
+pub async fn estimate_audit(
+    urls: Vec<String>,
+    config: Option<serde_json::Value>,
+    max_concurrent: Option<usize>,
+    history: State<'_, HistoryStore>,
+) -> Result<AuditEstimate, String> {
+    let config_key = config::config_key(&config.unwrap_or_default());
+    let fallback_ms = history
+        .average_duration_ms(None, config_key.as_deref())?
+        .map_or(DEFAULT_PER_URL_MS, |ms| ms.round() as u64);
+
+    let mut durations_ms = Vec::with_capacity(urls.len());
+    for url in &urls {
+        let url = validate::normalize_url(url).map_err(|e| format!("Invalid URL {}: {}", url, e))?;
+        let duration = history.average_duration_ms(Some(url.as_str()), config_key.as_deref())?;
+        durations_ms.push(duration.map_or(fallback_ms, |ms| ms.round() as u64));
+    }
+    Ok(estimate(
+        &durations_ms,
+        max_concurrent.unwrap_or_else(default_max_concurrent),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_concurrent_slots_in_order() {
+        let parallel = estimate(&[10_000, 4_000, 4_000, 4_000], 2);
+        assert_eq!(parallel.estimated_secs, 12);
+        assert_eq!(parallel.url_count, 4);
+        assert_eq!(parallel.avg_per_url_ms, 5_500);
+
+        assert_eq!(estimate(&[1_500, 1_500], 1).estimated_secs, 3);
+        assert_eq!(estimate(&[1_500], 0).estimated_secs, 2);
+        let empty = estimate(&[], 4);
+        assert_eq!((empty.estimated_secs, empty.url_count, empty.avg_per_url_ms), (0, 0, 0));
+    }
+}
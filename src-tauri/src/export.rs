@@ -0,0 +1,646 @@
+use crate::report::{CheckResult, SeoReport, Severity};
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference};
+use quick_xml::escape::escape;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A4, in millimetres.
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+const MM_PER_PT: f32 = 0.3528;
+
+/// How many file names `write_artifacts` tries before giving up.
+const MAX_ARTIFACT_ATTEMPTS: usize = 100;
+
+/// How many failed checks `summary_text` lists.
+const SUMMARY_ISSUES: usize = 3;
+
+/// Formats a report can be written in, with the file extension of each.
+pub const FORMATS: &[(&str, &str)] = &[
+    ("json", "json"),
+    ("csv", "csv"),
+    ("pdf", "pdf"),
+    ("html", "html"),
+    ("junit", "xml"),
+];
+
+/// The `FORMATS` entry for `format`, in any case.
+pub fn find_format(format: &str) -> Result<(&'static str, &'static str), String> {
+    FORMATS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(format.trim()))
+        .copied()
+        .ok_or_else(|| {
+            let names: Vec<&str> = FORMATS.iter().map(|(name, _)| *name).collect();
+            format!("Unsupported export format: {}. Use {}", format, names.join(", "))
+        })
+}
+
+/// Writes `report` to `path` in one of the `FORMATS`.
+pub fn write(report: &SeoReport, format: &str, path: &Path) -> Result<(), String> {
+    match find_format(format)?.0 {
+        "json" => write_json(&report.raw, path),
+        "csv" => write_csv(report, path),
+        "pdf" => write_pdf(report, path),
+        "html" => write_html(report, path),
+        _ => write_junit(report, path),
+    }
+}
+
+/// Writes `report` into `dir` once per format, as
+/// `<host>-<unix time in ms>.<ext>`, returning where each format went. When
+/// another check already wrote one of those files, all formats get the same
+/// `-2`, `-3`, ... suffix instead of overwriting it.
+pub fn write_artifacts(
+    report: &SeoReport,
+    formats: &[&str],
+    dir: &Path,
+) -> Result<BTreeMap<String, String>, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let host = url::Url::parse(&report.url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| "report".to_string());
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis());
+    // A format asked for twice is written once
+    let mut resolved = Vec::new();
+    for format in formats {
+        let format = find_format(format)?;
+        if !resolved.contains(&format) {
+            resolved.push(format);
+        }
+    }
+
+    let mut paths = None;
+    for attempt in 1..=MAX_ARTIFACT_ATTEMPTS {
+        let stem = match attempt {
+            1 => format!("{}-{}", host, now),
+            n => format!("{}-{}-{}", host, now, n),
+        };
+        let candidates: Vec<PathBuf> = resolved
+            .iter()
+            .map(|(_, extension)| dir.join(format!("{}.{}", stem, extension)))
+            .collect();
+        if claim(&candidates)? {
+            paths = Some(candidates);
+            break;
+        }
+    }
+    let paths = paths.ok_or_else(|| {
+        format!(
+            "Failed to find a free file name for {}-{} in {} after {} attempts",
+            host,
+            now,
+            dir.display(),
+            MAX_ARTIFACT_ATTEMPTS
+        )
+    })?;
+
+    let mut artifacts = BTreeMap::new();
+    for ((name, _), path) in resolved.iter().zip(paths) {
+        write(report, name, &path)?;
+        artifacts.insert(name.to_string(), path.display().to_string());
+    }
+    Ok(artifacts)
+}
+
+/// Creates every file in `paths`, returning false, with none of them left
+/// behind, if one already exists.
+fn claim(paths: &[PathBuf]) -> Result<bool, String> {
+    for (index, path) in paths.iter().enumerate() {
+        match File::options().write(true).create_new(true).open(path) {
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                for claimed in &paths[..index] {
+                    let _ = std::fs::remove_file(claimed);
+                }
+                return Ok(false);
+            }
+            Err(e) => return Err(format!("Failed to write {}: {}", path.display(), e)),
+        }
+    }
+    Ok(true)
+}
+
+/// Writes one row per check result: `check`, `status`, `message`, `severity`.
+///
+/// `check` is the checker category (e.g. `metaTags`), and `severity` is empty
+/// when the CLI didn't report one.
+pub fn write_csv(report: &SeoReport, path: &Path) -> Result<(), String> {
+    let to_error = |e: csv::Error| format!("Failed to write {}: {}", path.display(), e);
+
+    let mut writer = csv::Writer::from_path(path).map_err(to_error)?;
+    writer
+        .write_record(["check", "status", "message", "severity"])
+        .map_err(to_error)?;
+    for (category, results) in &report.checks {
+        for result in results {
+            writer
+                .write_record([
+                    category.as_str(),
+                    result.status(),
+                    result.message.as_str(),
+                    result.severity.map_or("", |severity| severity.as_str()),
+                ])
+                .map_err(to_error)?;
+        }
+    }
+    writer
+        .flush()
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Creates the missing parent directories of a file the app is about to
+/// write, failing early if it can't be written there.
+pub fn prepare_output_path(path: &Path) -> Result<(), String> {
+    if path.as_os_str().is_empty() || path.is_dir() {
+        return Err(format!("{} is not a file path", path.display()));
+    }
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Writes the CLI's untouched JSON output, pretty-printed.
+pub fn write_json(raw: &serde_json::Value, path: &Path) -> Result<(), String> {
+    let to_error = |e: std::io::Error| format!("Failed to write {}: {}", path.display(), e);
+
+    prepare_output_path(path)?;
+    let mut writer = BufWriter::new(File::create(path).map_err(to_error)?);
+    serde_json::to_writer_pretty(&mut writer, raw).map_err(|e| to_error(e.into()))?;
+    writer.write_all(b"\n").map_err(to_error)?;
+    writer.flush().map_err(to_error)
+}
+
+/// Writes a standalone HTML page: the score and summary, then a table of
+/// checks per checker category.
+pub fn write_html(report: &SeoReport, path: &Path) -> Result<(), String> {
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n",
+    );
+    html.push_str(&format!("<title>SEO report for {}</title>\n", escape(report.url.as_str())));
+    html.push_str(
+        "<style>body{font-family:sans-serif;margin:2em}table{border-collapse:collapse;width:100%}\
+         td,th{border:1px solid #ccc;padding:4px 8px;text-align:left}\
+         .pass{color:#1a7f37}.fail{color:#cf222e}</style>\n</head>\n<body>\n",
+    );
+    html.push_str(&format!("<h1>SEO report for {}</h1>\n", escape(report.url.as_str())));
+    if !report.timestamp.is_empty() {
+        html.push_str(&format!("<p>Checked {}</p>\n", escape(report.timestamp.as_str())));
+    }
+    let score = match (report.score, &report.grade) {
+        (Some(score), Some(grade)) => format!("{} ({})", score, grade),
+        (Some(score), None) => score.to_string(),
+        (None, _) => "n/a".to_string(),
+    };
+    html.push_str(&format!(
+        "<p><strong>Score: {}</strong>. {} passed, {} failed, {} total.</p>\n",
+        score, report.summary.passed, report.summary.failed, report.summary.total
+    ));
+
+    for (category, results) in &report.checks {
+        html.push_str(&format!(
+            "<h2>{}</h2>\n<table>\n<tr><th>Status</th><th>Message</th><th>Severity</th></tr>\n",
+            escape(category.as_str())
+        ));
+        for result in results {
+            html.push_str(&format!(
+                "<tr><td class=\"{0}\">{0}</td><td>{1}</td><td>{2}</td></tr>\n",
+                result.status(),
+                escape(result.message.as_str()),
+                result.severity.map_or("", |severity| severity.as_str())
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+
+    std::fs::write(path, html).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Writes a JUnit XML report for CI systems: a test suite per checker
+/// category and a test case per check, failed ones with their severity as
+/// the failure type.
+pub fn write_junit(report: &SeoReport, path: &Path) -> Result<(), String> {
+    let count = |results: &[CheckResult]| results.iter().filter(|result| !result.passed).count();
+    let total: usize = report.checks.values().map(Vec::len).sum();
+    let failed: usize = report.checks.values().map(|results| count(results)).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"SEO audit of {}\" tests=\"{}\" failures=\"{}\">\n",
+        escape(report.url.as_str()),
+        total,
+        failed
+    ));
+    for (category, results) in &report.checks {
+        let category = escape(category.as_str());
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+            category,
+            results.len(),
+            count(results)
+        ));
+        for result in results {
+            let message = escape(result.message.as_str());
+            xml.push_str(&format!("    <testcase classname=\"{}\" name=\"{}\"", category, message));
+            if result.passed {
+                xml.push_str("/>\n");
+            } else {
+                let severity = result.severity.map_or("failure", |severity| severity.as_str());
+                xml.push_str(&format!(
+                    ">\n      <failure type=\"{}\" message=\"{}\"/>\n    </testcase>\n",
+                    severity, message
+                ));
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+
+    std::fs::write(path, xml).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Renders the report as a PDF: a summary page with the score and the failed
+/// checks to fix, then one page per checker category.
+///
+/// Uses the PDF built-in Helvetica font, so nothing is downloaded or embedded;
+/// characters outside Latin-1 (such as emoji) can't be shown.
+pub fn write_pdf(report: &SeoReport, path: &Path) -> Result<(), String> {
+    let mut pdf = PdfWriter::new(&format!("SEO report for {}", report.url))?;
+
+    pdf.text("SEO Audit Report", 20.0, true);
+    pdf.gap(2.0);
+    pdf.text(&report.url, 12.0, false);
+    if !report.timestamp.is_empty() {
+        pdf.text(&format!("Checked {}", report.timestamp), 10.0, false);
+    }
+    pdf.gap(6.0);
+
+    pdf.text(&format!("Score: {}", score_text(report)), 16.0, true);
+    pdf.text(
+        &format!(
+            "{} passed, {} failed, {} total",
+            report.summary.passed, report.summary.failed, report.summary.total
+        ),
+        11.0,
+        false,
+    );
+    pdf.gap(6.0);
+
+    pdf.text("Recommendations", 14.0, true);
+    let failed = failed_checks(report);
+    if failed.is_empty() {
+        pdf.text("All checks passed.", 11.0, false);
+    }
+    for (category, result) in failed {
+        pdf.text(&format!("- {}: {}", category, describe(result)), 11.0, false);
+    }
+
+    for (category, results) in &report.checks {
+        pdf.new_page();
+        pdf.text(category, 16.0, true);
+        let passed = results.iter().filter(|result| result.passed).count();
+        pdf.text(&format!("{} of {} checks passed", passed, results.len()), 11.0, false);
+        pdf.gap(4.0);
+        for result in results {
+            let status = result.status().to_ascii_uppercase();
+            pdf.text(&format!("{}  {}", status, describe(result)), 11.0, false);
+        }
+    }
+
+    pdf.save(path)
+}
+
+/// The score with its grade and label, e.g. `85/100 (B, Good)`.
+fn score_text(report: &SeoReport) -> String {
+    match (report.score, &report.grade, &report.grade_label) {
+        (Some(score), Some(grade), Some(label)) => format!("{} ({}, {})", score, grade, label),
+        (Some(score), _, _) => score.to_string(),
+        (None, _, _) => "n/a".to_string(),
+    }
+}
+
+/// The failed checks with their categories, most severe first. Checks
+/// without a severity go last.
+fn failed_checks(report: &SeoReport) -> Vec<(&str, &CheckResult)> {
+    let mut failed: Vec<(&str, &CheckResult)> = report
+        .checks
+        .iter()
+        .flat_map(|(category, results)| results.iter().map(move |result| (category.as_str(), result)))
+        .filter(|(_, result)| !result.passed)
+        .collect();
+    failed.sort_by_key(|(_, result)| Reverse(result.severity));
+    failed
+}
+
+/// A few plain-text lines for pasting into chat or email: the URL, score,
+/// check counts and the `SUMMARY_ISSUES` most severe failed checks. Failed
+/// checks with a `warning` or `info` severity count as warnings.
+pub fn summary_text(report: &SeoReport) -> String {
+    let failed = failed_checks(report);
+    let warnings = failed
+        .iter()
+        .filter(|(_, result)| matches!(result.severity, Some(Severity::Warning | Severity::Info)))
+        .count();
+    let passed = report.checks.values().flatten().filter(|result| result.passed).count();
+
+    let mut lines = vec![
+        format!("SEO report for {}", report.url),
+        format!("Score: {}", score_text(report)),
+        format!("{} passed, {} warnings, {} failed", passed, warnings, failed.len() - warnings),
+    ];
+    if failed.is_empty() {
+        lines.push("No issues found".to_string());
+    } else {
+        lines.push("Top issues:".to_string());
+        for (category, result) in failed.iter().take(SUMMARY_ISSUES) {
+            lines.push(format!("- {}: {}", category, describe(result)));
+        }
+    }
+    lines.join("\n")
+}
+
+/// A check's message, prefixed with its severity when it has one.
+fn describe(result: &CheckResult) -> String {
+    match result.severity {
+        Some(severity) => format!("[{}] {}", severity.as_str(), result.message),
+        None => result.message.clone(),
+    }
+}
+
+/// Lays text out top to bottom, wrapping long lines and starting a new page
+/// when one fills up.
+struct PdfWriter {
+    doc: PdfDocumentReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    layer: PdfLayerReference,
+    /// Baseline of the next line, in millimetres from the bottom of the page.
+    y: f32,
+}
+
+impl PdfWriter {
+    fn new(title: &str) -> Result<Self, String> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        let font = |font| {
+            doc.add_builtin_font(font)
+                .map_err(|e| format!("Failed to load PDF font: {}", e))
+        };
+        let regular = font(BuiltinFont::Helvetica)?;
+        let bold = font(BuiltinFont::HelveticaBold)?;
+        let layer = doc.get_page(page).get_layer(layer);
+
+        Ok(Self {
+            doc,
+            regular,
+            bold,
+            layer,
+            y: PAGE_HEIGHT - MARGIN,
+        })
+    }
+
+    fn new_page(&mut self) {
+        let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        self.layer = self.doc.get_page(page).get_layer(layer);
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    fn gap(&mut self, mm: f32) {
+        self.y -= mm;
+    }
+
+    fn text(&mut self, text: &str, size: f32, bold: bool) {
+        let line_height = size * MM_PER_PT * 1.4;
+        // Helvetica averages about half an em per character
+        let max_chars = ((PAGE_WIDTH - 2.0 * MARGIN) / (size * MM_PER_PT * 0.5)) as usize;
+
+        for line in wrap(text, max_chars) {
+            if self.y - line_height < MARGIN {
+                self.new_page();
+            }
+            self.y -= line_height;
+            let font = if bold { &self.bold } else { &self.regular };
+            self.layer.use_text(line, size, Mm(MARGIN), Mm(self.y), font);
+        }
+    }
+
+    fn save(self, path: &Path) -> Result<(), String> {
+        let file = File::create(path)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        self.doc
+            .save(&mut BufWriter::new(file))
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+/// Greedily wraps `text` at word boundaries into lines of at most
+/// `max_chars` characters, splitting words that are longer than a line.
+fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word: Vec<char> = word.chars().collect();
+            while word.len() > max_chars {
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                lines.push(word.drain(..max_chars).collect());
+            }
+            let word: String = word.into_iter().collect();
+
+            let len = line.chars().count();
+            if len > 0 && len + 1 + word.chars().count() > max_chars {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+#[tauri::command]
+pub async fn export_report(report: SeoReport, format: String, path: String) -> Result<(), String> {
+    write(&report, &format, Path::new(&path))
+}
+
+#[tauri::command]
+pub async fn format_summary(report: SeoReport) -> Result<String, String> {
+    Ok(summary_text(&report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn writes_one_quoted_row_per_check() {
+        let report = SeoReport::from_value(json!({
+            "checks": {
+                "images": [{ "passed": false, "message": "Missing alt text, 2 images\nsee details", "severity": "error" }],
+                "metaTags": [{ "passed": true, "message": "Title exists" }]
+            }
+        }))
+        .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.csv");
+        write_csv(&report, &path).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "check,status,message,severity\n\
+             images,fail,\"Missing alt text, 2 images\nsee details\",error\n\
+             metaTags,pass,Title exists,\n"
+        );
+    }
+
+    #[test]
+    fn wraps_at_word_boundaries() {
+        assert_eq!(wrap("one two three four", 9), vec!["one two", "three", "four"]);
+        assert_eq!(wrap("https://example.com/x", 8), vec!["https://", "example.", "com/x"]);
+        assert_eq!(wrap("first\nsecond", 20), vec!["first", "second"]);
+    }
+
+    #[test]
+    fn writes_a_pdf() {
+        let report = SeoReport::from_value(json!({
+            "url": "https://example.com/",
+            "score": 72,
+            "summary": { "total": 2, "passed": 1, "failed": 1 },
+            "checks": {
+                "metaTags": [
+                    { "passed": true, "message": "Title exists" },
+                    { "passed": false, "message": "Description too short ".repeat(20), "severity": "warning" }
+                ]
+            }
+        }))
+        .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.pdf");
+        write_pdf(&report, &path).unwrap();
+        assert!(std::fs::read(&path).unwrap().starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn writes_raw_json_creating_parent_directories() {
+        let raw = json!({ "url": "https://example.com/", "futureField": [1, 2] });
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reports/2024/example.json");
+        write_json(&raw, &path).unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.contains("\n  \"futureField\": ["), "{}", written);
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&written).unwrap(), raw);
+        assert!(prepare_output_path(dir.path()).unwrap_err().contains("not a file path"));
+    }
+
+    #[test]
+    fn writes_escaped_html_and_junit() {
+        let report = SeoReport::from_value(json!({
+            "url": "https://example.com/?a=1&b=2",
+            "score": 72,
+            "checks": {
+                "metaTags": [
+                    { "passed": true, "message": "Title exists" },
+                    { "passed": false, "message": "Title contains <script>", "severity": "error" }
+                ]
+            }
+        }))
+        .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let path = dir.path().join("report.html");
+        write(&report, "HTML", &path).unwrap();
+        let html = std::fs::read_to_string(&path).unwrap();
+        assert!(html.contains("<title>SEO report for https://example.com/?a=1&amp;b=2</title>"));
+        assert!(html.contains("<td>Title contains &lt;script&gt;</td><td>error</td>"), "{}", html);
+
+        let path = dir.path().join("report.xml");
+        write(&report, "junit", &path).unwrap();
+        let xml = std::fs::read_to_string(&path).unwrap();
+        assert!(xml.contains("<testsuite name=\"metaTags\" tests=\"2\" failures=\"1\">"), "{}", xml);
+        assert!(xml.contains("<testcase classname=\"metaTags\" name=\"Title exists\"/>"));
+        assert!(xml.contains("<failure type=\"error\" message=\"Title contains &lt;script&gt;\"/>"));
+
+        assert!(write(&report, "docx", &path).unwrap_err().contains("Use json, csv, pdf, html, junit"));
+
+        let artifacts = write_artifacts(&report, &["json", "junit"], &dir.path().join("out")).unwrap();
+        assert_eq!(artifacts.keys().collect::<Vec<_>>(), ["json", "junit"]);
+        assert!(artifacts["junit"].contains("example.com-") && artifacts["junit"].ends_with(".xml"));
+        assert!(Path::new(&artifacts["json"]).is_file());
+
+        // Checks finishing at the same moment don't overwrite each other
+        let runs: Vec<_> = (0..3)
+            .map(|_| write_artifacts(&report, &["html", "junit"], &dir.path().join("same")).unwrap())
+            .collect();
+        let mut paths: Vec<&String> = runs.iter().flat_map(|run| run.values()).collect();
+        paths.sort();
+        paths.dedup();
+        assert_eq!(paths.len(), 6);
+        assert_eq!(std::fs::read_dir(dir.path().join("same")).unwrap().count(), 6);
+
+        let twice = write_artifacts(&report, &["json", "JSON", "html"], &dir.path().join("twice")).unwrap();
+        assert_eq!(twice.keys().collect::<Vec<_>>(), ["html", "json"]);
+        assert_eq!(std::fs::read_dir(dir.path().join("twice")).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn summarizes_the_top_issues_as_plain_text() {
+        let report = SeoReport::from_value(json!({
+            "url": "https://example.com/",
+            "score": 72,
+            "checks": {
+                "images": [
+                    { "passed": false, "message": "2 images without alt text", "severity": "warning" },
+                    { "passed": true, "message": "Images are lazy-loaded" }
+                ],
+                "metaTags": [
+                    { "passed": true, "message": "Title exists" },
+                    { "passed": false, "message": "Description missing", "severity": "error" },
+                    { "passed": false, "message": "Keywords set", "severity": "info" },
+                    { "passed": false, "message": "Viewport missing" }
+                ]
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            summary_text(&report),
+            "SEO report for https://example.com/\n\
+             Score: 72/100 (C, Needs work)\n\
+             2 passed, 2 warnings, 2 failed\n\
+             Top issues:\n\
+             - metaTags: [error] Description missing\n\
+             - images: [warning] 2 images without alt text\n\
+             - metaTags: [info] Keywords set"
+        );
+
+        let clean = SeoReport::from_value(json!({ "url": "https://example.com/" })).unwrap();
+        assert!(summary_text(&clean).ends_with("Score: n/a\n0 passed, 0 warnings, 0 failed\nNo issues found"));
+    }
+
+    #[test]
+    fn reports_unwritable_paths() {
+        let report = SeoReport::from_value(json!({})).unwrap();
+        let error = write_csv(&report, Path::new("/nonexistent/dir/report.csv")).unwrap_err();
+        assert!(error.starts_with("Failed to write /nonexistent/dir/report.csv"), "{}", error);
+    }
+}
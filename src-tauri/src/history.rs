@@ -0,0 +1,547 @@
+use crate::report::{Score, SeoReport};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use rusqlite::types::ValueRef;
+use rusqlite::{params, Connection, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::State;
+
+const DB_FILE_NAME: &str = "history.sqlite3";
+
+/// Smallest score change per day, either way, that counts as a trend.
+const TREND_THRESHOLD: f64 = 0.1;
+
+const SECS_PER_DAY: f64 = 86_400.0;
+
+/// How many of the latest checks an average duration covers, so it follows
+/// changes to the site or the machine.
+const RECENT_DURATIONS: i64 = 20;
+
+/// First bytes of every gzip stream, telling compressed reports apart from
+/// the plain JSON text older versions stored.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Gzips a report's JSON for the `report` column.
+fn compress(json: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(json)?;
+    encoder.finish()
+}
+
+/// Reads the `report` column at `index` of `row`, gzipped or plain JSON.
+/// Unreadable reports become `null` rather than hiding the rest of the
+/// history.
+fn decode_report(row: &Row<'_>, index: usize) -> rusqlite::Result<serde_json::Value> {
+    let (ValueRef::Blob(stored) | ValueRef::Text(stored)) = row.get_ref(index)? else {
+        return Ok(serde_json::Value::Null);
+    };
+    let parsed = if stored.starts_with(&GZIP_MAGIC) {
+        let mut json = Vec::new();
+        GzDecoder::new(stored)
+            .read_to_end(&mut json)
+            .map_err(|e| e.to_string())
+            .and_then(|_| serde_json::from_slice(&json).map_err(|e| e.to_string()))
+    } else {
+        serde_json::from_slice(stored).map_err(|e| e.to_string())
+    };
+    Ok(parsed.unwrap_or_else(|e| {
+        log::warn!("Ignoring unreadable history report: {}", e);
+        serde_json::Value::Null
+    }))
+}
+
+/// A completed check as stored in the history database.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: i64,
+    /// Unix timestamp (seconds) of when the check completed.
+    pub checked_at: i64,
+    pub url: String,
+    pub config: Option<String>,
+    pub score: Option<f64>,
+    /// The full JSON report as returned by the CLI.
+    pub report: serde_json::Value,
+}
+
+/// Which way scores are heading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Trend {
+    Improving,
+    Stable,
+    Declining,
+}
+
+/// How often one check failed, with its checker category and the message
+/// it failed with most recently.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckFailures {
+    pub category: String,
+    pub message: String,
+    pub count: u32,
+}
+
+/// Aggregates over a page's, or every page's, history.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HistoryStats {
+    /// How many history entries these cover.
+    pub checks: usize,
+    pub average_score: Option<f64>,
+    pub min_score: Option<f64>,
+    pub max_score: Option<f64>,
+    /// Slope of a least-squares line through the scores over time.
+    pub score_change_per_day: Option<f64>,
+    /// `None` until there are scores from at least two points in time.
+    pub trend: Option<Trend>,
+    /// Failed checks by `category:message`, with numbers in the message
+    /// ignored so a changing count is still the same check.
+    pub failures: BTreeMap<String, CheckFailures>,
+    /// The key in `failures` with the highest count.
+    pub most_common_failure: Option<String>,
+}
+
+/// Computes `HistoryStats` from `(checked_at, report)` pairs, oldest first.
+fn aggregate(entries: &[(i64, SeoReport)]) -> HistoryStats {
+    let scores: Vec<(f64, f64)> = entries
+        .iter()
+        .filter_map(|(checked_at, report)| Some((*checked_at as f64 / SECS_PER_DAY, report.score?.percent())))
+        .collect();
+
+    let mut stats = HistoryStats {
+        checks: entries.len(),
+        min_score: scores.iter().map(|(_, score)| *score).reduce(f64::min),
+        max_score: scores.iter().map(|(_, score)| *score).reduce(f64::max),
+        ..HistoryStats::default()
+    };
+    if !scores.is_empty() {
+        let count = scores.len() as f64;
+        let mean_day = scores.iter().map(|(day, _)| day).sum::<f64>() / count;
+        let mean_score = scores.iter().map(|(_, score)| score).sum::<f64>() / count;
+        stats.average_score = Some(mean_score);
+
+        let spread: f64 = scores.iter().map(|(day, _)| (day - mean_day).powi(2)).sum();
+        if spread > 0.0 {
+            let covariance: f64 = scores
+                .iter()
+                .map(|(day, score)| (day - mean_day) * (score - mean_score))
+                .sum();
+            let slope = covariance / spread;
+            stats.score_change_per_day = Some(slope);
+            stats.trend = Some(if slope >= TREND_THRESHOLD {
+                Trend::Improving
+            } else if slope <= -TREND_THRESHOLD {
+                Trend::Declining
+            } else {
+                Trend::Stable
+            });
+        }
+    }
+
+    for (_, report) in entries {
+        for (category, results) in &report.checks {
+            for result in results.iter().filter(|result| !result.passed) {
+                let key = format!("{}:{}", category, crate::diff::message_key(&result.message));
+                let failures = stats.failures.entry(key).or_insert_with(|| CheckFailures {
+                    category: category.clone(),
+                    message: String::new(),
+                    count: 0,
+                });
+                failures.message = result.message.clone();
+                failures.count += 1;
+            }
+        }
+    }
+    // Ties go to the first key, so the answer doesn't flip between calls
+    stats.most_common_failure = stats
+        .failures
+        .iter()
+        .rev()
+        .max_by_key(|(_, failures)| failures.count)
+        .map(|(key, _)| key.clone());
+    stats
+}
+
+/// SQLite-backed check history.
+///
+/// A single connection behind a mutex serializes writes from concurrent
+/// batch checks. Reports are stored gzipped, as blobs.
+pub struct HistoryStore {
+    conn: Mutex<Connection>,
+}
+
+impl HistoryStore {
+    /// Opens (creating if needed) the history database in `dir`.
+    pub fn open(dir: &Path) -> Result<Self, String> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let conn = Connection::open(dir.join(DB_FILE_NAME))
+            .map_err(|e| format!("Failed to open history database: {}", e))?;
+        Self::with_connection(conn)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, String> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS check_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                checked_at INTEGER NOT NULL,
+                url TEXT NOT NULL,
+                config TEXT,
+                score REAL,
+                report TEXT NOT NULL,
+                duration_ms INTEGER
+            );
+            CREATE INDEX IF NOT EXISTS check_history_url ON check_history (url, checked_at);",
+        )
+        .map_err(|e| format!("Failed to create history schema: {}", e))?;
+        Self::add_duration_column(&conn)?;
+        Self::compress_plain_reports(&conn)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Adds the `duration_ms` column to databases created before checks
+    /// were timed. Their entries keep a `NULL` duration.
+    fn add_duration_column(conn: &Connection) -> Result<(), String> {
+        let to_error = |e: rusqlite::Error| format!("Failed to migrate history schema: {}", e);
+        let exists: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('check_history') WHERE name = 'duration_ms'",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(to_error)?;
+        if !exists {
+            conn.execute_batch("ALTER TABLE check_history ADD COLUMN duration_ms INTEGER")
+                .map_err(to_error)?;
+        }
+        Ok(())
+    }
+
+    /// Gzips the plain JSON reports stored before compression was added,
+    /// then reclaims the space they took.
+    fn compress_plain_reports(conn: &Connection) -> Result<(), String> {
+        let to_error = |e: rusqlite::Error| format!("Failed to compress history: {}", e);
+
+        let plain: Vec<(i64, String)> = conn
+            .prepare("SELECT id, report FROM check_history WHERE typeof(report) = 'text'")
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                    .collect()
+            })
+            .map_err(to_error)?;
+        if plain.is_empty() {
+            return Ok(());
+        }
+
+        let tx = conn.unchecked_transaction().map_err(to_error)?;
+        for (id, json) in &plain {
+            let compressed = compress(json.as_bytes())
+                .map_err(|e| format!("Failed to compress history: {}", e))?;
+            tx.execute(
+                "UPDATE check_history SET report = ?1 WHERE id = ?2",
+                params![compressed, id],
+            )
+            .map_err(to_error)?;
+        }
+        tx.commit().map_err(to_error)?;
+        conn.execute_batch("VACUUM").map_err(to_error)?;
+        log::info!("Compressed {} history reports", plain.len());
+        Ok(())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Saves a check of `url` that took `duration_ms`, including launching
+    /// the CLI.
+    pub fn record(
+        &self,
+        url: &str,
+        config: Option<&str>,
+        report: &SeoReport,
+        duration_ms: u64,
+    ) -> Result<i64, String> {
+        let checked_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64);
+        let json = serde_json::to_vec(&report.raw)
+            .map_err(|e| format!("Failed to serialize report: {}", e))?;
+        let compressed = compress(&json).map_err(|e| format!("Failed to compress report: {}", e))?;
+
+        let conn = self.lock();
+        conn.execute(
+            "INSERT INTO check_history (checked_at, url, config, score, report, duration_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![checked_at, url, config, report.score.map(Score::percent), compressed, duration_ms as i64],
+        )
+        .map_err(|e| format!("Failed to record check history: {}", e))?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Returns the newest entries first, optionally only those for `url`.
+    pub fn list(&self, url: Option<&str>, limit: usize) -> Result<Vec<HistoryEntry>, String> {
+        let conn = self.lock();
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, checked_at, url, config, score, report FROM check_history
+                 WHERE ?1 IS NULL OR url = ?1
+                 ORDER BY checked_at DESC, id DESC
+                 LIMIT ?2",
+            )
+            .map_err(|e| format!("Failed to query history: {}", e))?;
+
+        let rows = stmt
+            .query_map(params![url, limit as i64], |row| {
+                Ok(HistoryEntry {
+                    id: row.get(0)?,
+                    checked_at: row.get(1)?,
+                    url: row.get(2)?,
+                    config: row.get(3)?,
+                    score: row.get(4)?,
+                    report: decode_report(row, 5)?,
+                })
+            })
+            .map_err(|e| format!("Failed to query history: {}", e))?;
+
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to read history: {}", e))
+    }
+
+    /// Average duration of the latest `RECENT_DURATIONS` timed checks with
+    /// `config`, optionally only those of `url`; `None` if there are none.
+    pub fn average_duration_ms(&self, url: Option<&str>, config: Option<&str>) -> Result<Option<f64>, String> {
+        self.lock()
+            .query_row(
+                "SELECT AVG(duration_ms) FROM (
+                     SELECT duration_ms FROM check_history
+                     WHERE duration_ms IS NOT NULL AND (?1 IS NULL OR url = ?1) AND config IS ?2
+                     ORDER BY checked_at DESC, id DESC
+                     LIMIT ?3
+                 )",
+                params![url, config, RECENT_DURATIONS],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Failed to query history: {}", e))
+    }
+
+    /// Aggregates the entries checked at or after `since` (a Unix
+    /// timestamp), optionally only those for `url`.
+    pub fn stats(&self, url: Option<&str>, since: Option<i64>) -> Result<HistoryStats, String> {
+        let conn = self.lock();
+        let mut stmt = conn
+            .prepare(
+                "SELECT checked_at, report FROM check_history
+                 WHERE (?1 IS NULL OR url = ?1) AND (?2 IS NULL OR checked_at >= ?2)
+                 ORDER BY checked_at, id",
+            )
+            .map_err(|e| format!("Failed to query history: {}", e))?;
+
+        let rows = stmt
+            .query_map(params![url, since], |row| Ok((row.get::<_, i64>(0)?, decode_report(row, 1)?)))
+            .map_err(|e| format!("Failed to query history: {}", e))?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            let (checked_at, report) = row.map_err(|e| format!("Failed to read history: {}", e))?;
+            // `decode_report` already warned about it
+            if report.is_null() {
+                continue;
+            }
+            match SeoReport::from_value(report) {
+                Ok(report) => entries.push((checked_at, report)),
+                Err(e) => log::warn!("Leaving an unreadable report out of history stats: {}", e),
+            }
+        }
+        Ok(aggregate(&entries))
+    }
+
+    pub fn delete(&self, id: i64) -> Result<(), String> {
+        let deleted = self
+            .lock()
+            .execute("DELETE FROM check_history WHERE id = ?1", params![id])
+            .map_err(|e| format!("Failed to delete history entry: {}", e))?;
+
+        if deleted == 0 {
+            return Err(format!("No history entry with id {}", id));
+        }
+        Ok(())
+    }
+
+    /// Deletes every entry, returning how many there were, and vacuums the
+    /// database so the deleted reports don't linger in free pages.
+    pub fn clear(&self) -> Result<usize, String> {
+        let conn = self.lock();
+        let deleted = conn
+            .execute("DELETE FROM check_history", [])
+            .map_err(|e| format!("Failed to clear history: {}", e))?;
+        conn.execute_batch("VACUUM")
+            .map_err(|e| format!("Failed to clear history: {}", e))?;
+        Ok(deleted)
+    }
+}
+
+#[tauri::command]
+pub async fn get_check_history(
+    url: Option<String>,
+    limit: usize,
+    history: State<'_, HistoryStore>,
+) -> Result<Vec<HistoryEntry>, String> {
+    // Entries are stored under the normalized URL
+    let url = url.map(|url| {
+        crate::validate::normalize_url(&url).map_or(url, |normalized| normalized.to_string())
+    });
+    history.list(url.as_deref(), limit)
+}
+
+#[tauri::command]
+pub async fn get_history_stats(
+    url: Option<String>,
+    since: Option<i64>,
+    history: State<'_, HistoryStore>,
+) -> Result<HistoryStats, String> {
+    let url = url.map(|url| {
+        crate::validate::normalize_url(&url).map_or(url, |normalized| normalized.to_string())
+    });
+    history.stats(url.as_deref(), since)
+}
+
+#[tauri::command]
+pub async fn delete_history_entry(id: i64, history: State<'_, HistoryStore>) -> Result<(), String> {
+    history.delete(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn report(url: &str, score: f64) -> SeoReport {
+        SeoReport::from_value(json!({ "url": url, "score": score })).unwrap()
+    }
+
+    #[test]
+    fn records_lists_and_deletes_entries() {
+        let store = HistoryStore::with_connection(Connection::open_in_memory().unwrap()).unwrap();
+        let first = store.record("https://a.example/", None, &report("https://a.example/", 70.0), 4_000).unwrap();
+        store.record("https://b.example/", Some("basic"), &report("https://b.example/", 90.0), 9_000).unwrap();
+        store.record("https://a.example/", None, &report("https://a.example/", 80.0), 6_000).unwrap();
+
+        let all = store.list(None, 10).unwrap();
+        assert_eq!(all.len(), 3);
+
+        let a = store.list(Some("https://a.example/"), 10).unwrap();
+        assert_eq!(a.iter().map(|e| e.score).collect::<Vec<_>>(), vec![Some(80.0), Some(70.0)]);
+        assert_eq!(a[0].report["score"], json!(80.0));
+
+        assert_eq!(store.average_duration_ms(Some("https://a.example/"), None).unwrap(), Some(5_000.0));
+        assert_eq!(store.average_duration_ms(None, Some("basic")).unwrap(), Some(9_000.0));
+        assert_eq!(store.average_duration_ms(Some("https://a.example/"), Some("basic")).unwrap(), None);
+
+        store.delete(first).unwrap();
+        assert_eq!(store.list(Some("https://a.example/"), 10).unwrap().len(), 1);
+        assert!(store.delete(first).is_err());
+
+        assert_eq!(store.clear().unwrap(), 2);
+        assert!(store.list(None, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn aggregates_scores_trends_and_failures() {
+        let day = SECS_PER_DAY as i64;
+        let entry = |days: i64, score: f64, failed: &[&str]| {
+            let failed: Vec<_> = failed.iter().map(|message| json!({ "passed": false, "message": message })).collect();
+            let report = SeoReport::from_value(json!({
+                "score": score,
+                "checks": { "metaTags": failed, "images": [{ "passed": true, "message": "Alt text set" }] }
+            }));
+            (days * day, report.unwrap())
+        };
+        let entries = [
+            entry(0, 60.0, &["Title too long (72 chars)", "Description missing"]),
+            entry(1, 70.0, &["Title too long (65 chars)"]),
+            entry(2, 80.0, &["Title too long (61 chars)"]),
+        ];
+
+        let stats = aggregate(&entries);
+        assert_eq!(stats.checks, 3);
+        assert_eq!(stats.average_score, Some(70.0));
+        assert_eq!((stats.min_score, stats.max_score), (Some(60.0), Some(80.0)));
+        assert!((stats.score_change_per_day.unwrap() - 10.0).abs() < 1e-9);
+        assert_eq!(stats.trend, Some(Trend::Improving));
+
+        let title = &stats.failures["metaTags:title too long (# chars)"];
+        assert_eq!((title.count, title.message.as_str()), (3, "Title too long (61 chars)"));
+        assert_eq!(stats.failures["metaTags:description missing"].count, 1);
+        assert_eq!(stats.most_common_failure.as_deref(), Some("metaTags:title too long (# chars)"));
+
+        let declining = [entry(0, 90.0, &[]), entry(10, 50.0, &[])];
+        assert_eq!(aggregate(&declining).trend, Some(Trend::Declining));
+        let flat = [entry(0, 90.0, &[]), entry(30, 91.0, &[])];
+        assert_eq!(aggregate(&flat).trend, Some(Trend::Stable));
+        assert_eq!(aggregate(&entries[..1]).trend, None);
+        assert_eq!(aggregate(&[]), HistoryStats::default());
+    }
+
+    #[test]
+    fn stores_reports_gzipped() {
+        let store = HistoryStore::with_connection(Connection::open_in_memory().unwrap()).unwrap();
+        let report = SeoReport::from_value(json!({
+            "url": "https://a.example/",
+            "score": 75,
+            "checks": { "images": vec![json!({ "passed": false, "message": "Missing alt text" }); 50] }
+        }))
+        .unwrap();
+        store.record("https://a.example/", None, &report, 1_000).unwrap();
+
+        let stored: Vec<u8> = store
+            .lock()
+            .query_row("SELECT report FROM check_history", [], |row| row.get(0))
+            .unwrap();
+        assert!(stored.starts_with(&GZIP_MAGIC));
+        assert!(stored.len() < serde_json::to_vec(&report.raw).unwrap().len() / 4);
+        assert_eq!(store.list(None, 1).unwrap()[0].report, report.raw);
+    }
+
+    #[test]
+    fn compresses_reports_stored_as_plain_json() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            r#"CREATE TABLE check_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                checked_at INTEGER NOT NULL,
+                url TEXT NOT NULL,
+                config TEXT,
+                score REAL,
+                report TEXT NOT NULL
+            );
+            INSERT INTO check_history (checked_at, url, score, report)
+            VALUES (1, 'https://a.example/', 60, '{"url":"https://a.example/","score":60}');"#,
+        )
+        .unwrap();
+
+        let store = HistoryStore::with_connection(conn).unwrap();
+        let stored: Vec<u8> = store
+            .lock()
+            .query_row("SELECT report FROM check_history", [], |row| row.get(0))
+            .unwrap();
+        assert!(stored.starts_with(&GZIP_MAGIC));
+        assert_eq!(
+            store.list(None, 1).unwrap()[0].report,
+            json!({ "url": "https://a.example/", "score": 60 })
+        );
+
+        // Checks from before durations were recorded have none
+        assert_eq!(store.average_duration_ms(None, None).unwrap(), None);
+        store.record("https://a.example/", None, &report("https://a.example/", 60.0), 2_000).unwrap();
+        assert_eq!(store.average_duration_ms(None, None).unwrap(), Some(2_000.0));
+    }
+}
@@ -0,0 +1,68 @@
+use crate::validate;
+use std::collections::HashMap;
+use std::time::Duration;
+
+const APP_USER_AGENT: &str = concat!("e2e-seo-desktop/", env!("CARGO_PKG_VERSION"));
+
+/// Builds an HTTP client for the app's own requests (sitemaps, webhooks),
+/// identifying itself as the desktop app.
+pub fn client(timeout: Duration) -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .user_agent(APP_USER_AGENT)
+        .build()
+        .map_err(build_error)
+}
+
+/// Builds a client that fetches pages the way a check would, with the
+/// check's user agent, extra headers and proxy, validated as for the CLI.
+pub fn page_client(
+    timeout: Duration,
+    user_agent: Option<&str>,
+    headers: &HashMap<String, String>,
+    proxy: Option<&str>,
+) -> Result<reqwest::Client, String> {
+    page_client_builder(timeout, user_agent, headers, proxy)?
+        .build()
+        .map_err(build_error)
+}
+
+/// `page_client`'s configuration, for callers that need to change more.
+pub fn page_client_builder(
+    timeout: Duration,
+    user_agent: Option<&str>,
+    headers: &HashMap<String, String>,
+    proxy: Option<&str>,
+) -> Result<reqwest::ClientBuilder, String> {
+    let mut builder = reqwest::Client::builder().timeout(timeout);
+
+    match user_agent {
+        Some(user_agent) => {
+            validate::user_agent(user_agent).map_err(|e| format!("Invalid user agent: {}", e))?;
+            builder = builder.user_agent(user_agent);
+        }
+        None => builder = builder.user_agent(APP_USER_AGENT),
+    }
+
+    let mut header_map = reqwest::header::HeaderMap::new();
+    for (name, value) in headers {
+        validate::header(name, value).map_err(|e| format!("Invalid header: {}", e))?;
+        let name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| format!("Invalid header name {}: {}", name, e))?;
+        let value = reqwest::header::HeaderValue::from_str(value.trim())
+            .map_err(|e| format!("Invalid value for header {}: {}", name, e))?;
+        header_map.insert(name, value);
+    }
+    builder = builder.default_headers(header_map);
+
+    if let Some(proxy) = proxy {
+        let proxy = validate::proxy(proxy).map_err(|e| format!("Invalid proxy: {}", e))?;
+        let proxy = reqwest::Proxy::all(proxy.as_str()).map_err(|e| format!("Invalid proxy: {}", e))?;
+        builder = builder.proxy(proxy);
+    }
+    Ok(builder)
+}
+
+fn build_error(e: reqwest::Error) -> String {
+    format!("Failed to create HTTP client: {}", e)
+}
@@ -0,0 +1,121 @@
+use crate::error::SeoError;
+use crate::SeoCheckResult;
+use std::sync::{Mutex, PoisonError};
+use tauri::State;
+
+/// Locale used until the frontend calls `set_locale`.
+const DEFAULT_LOCALE: &str = "en";
+
+/// `SeoError` messages by language and error code. English comes from
+/// `SeoError`'s `Display` impl and is the fallback for anything missing here.
+///
+/// `{message}` and `{secs}` are replaced with the error's fields. Details in
+/// `{message}` come from the CLI or the OS and stay in English.
+const TRANSLATIONS: &[(&str, &[(&str, &str)])] = &[(
+    "de",
+    &[
+        ("cli_not_found", "SEO-Prüfprogramm nicht gefunden: {message}"),
+        ("spawn_failed", "SEO-Prüfprogramm konnte nicht gestartet werden: {message}"),
+        ("timeout", "Zeitüberschreitung: Die SEO-Prüfung hat länger als {secs} Sekunden gedauert"),
+        ("cancelled", "Die SEO-Prüfung wurde abgebrochen"),
+        ("invalid_url", "Ungültige URL: {message}"),
+        ("unreachable", "Seite nicht erreichbar: {message}"),
+        ("invalid_config", "Ungültige Konfiguration: {message}"),
+        ("invalid_argument", "Ungültiges Argument: {message}"),
+        ("json_parse_error", "Die Ausgabe des SEO-Prüfprogramms ist kein gültiges JSON: {message}"),
+    ],
+)];
+
+/// The locale for user-facing messages, set by the frontend.
+#[derive(Default)]
+pub struct LocaleState {
+    locale: Mutex<Option<String>>,
+}
+
+impl LocaleState {
+    pub fn get(&self) -> String {
+        self.locale
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+            .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+    }
+}
+
+/// The error's message in `locale` (e.g. `de` or `de-AT`), falling back to English.
+pub fn localize(error: &SeoError, locale: &str) -> String {
+    let language = locale
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    let template = TRANSLATIONS
+        .iter()
+        .find(|(lang, _)| *lang == language)
+        .and_then(|(_, messages)| messages.iter().find(|(code, _)| *code == error.code()))
+        .map(|(_, template)| *template);
+    let Some(template) = template else {
+        return error.to_string();
+    };
+
+    let (message, secs) = match error {
+        SeoError::CliNotFound { message }
+        | SeoError::UnsupportedCli { message }
+        | SeoError::SpawnFailed { message }
+        | SeoError::InvalidUrl { message }
+        | SeoError::InvalidConfig { message }
+        | SeoError::InvalidArgument { message }
+        | SeoError::Unreachable { message, .. }
+        | SeoError::JsonParseError { message } => (message.as_str(), None),
+        SeoError::Timeout { secs } => ("", Some(*secs)),
+        SeoError::Cancelled
+        | SeoError::OutputTooLarge { .. }
+        | SeoError::CliOutputEncoding { .. }
+        | SeoError::SchemaMismatch { .. } => ("", None),
+        SeoError::CliExitError { stderr, .. } => (stderr.as_str(), None),
+    };
+    template
+        .replace("{message}", message)
+        .replace("{secs}", &secs.map_or_else(String::new, |secs| secs.to_string()))
+}
+
+/// Rewrites a failed result's `error` text in `locale`.
+pub fn localize_result(mut result: SeoCheckResult, locale: &str) -> SeoCheckResult {
+    if let Some(kind) = &result.error_kind {
+        result.error = Some(localize(kind, locale));
+    }
+    result
+}
+
+#[tauri::command]
+pub async fn set_locale(locale: String, state: State<'_, LocaleState>) -> Result<(), String> {
+    *state.locale.lock().unwrap_or_else(PoisonError::into_inner) = Some(locale);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translates_known_errors() {
+        let timeout = SeoError::Timeout { secs: 60 };
+        assert_eq!(localize(&timeout, "de"), "Zeitüberschreitung: Die SEO-Prüfung hat länger als 60 Sekunden gedauert");
+        assert_eq!(localize(&timeout, "de_AT"), localize(&timeout, "de"));
+        assert_eq!(localize(&timeout, "en-US"), "SEO check timed out after 60 seconds");
+
+        let invalid = SeoError::InvalidUrl { message: "URL is empty".to_string() };
+        assert_eq!(localize(&invalid, "DE-de"), "Ungültige URL: URL is empty");
+    }
+
+    #[test]
+    fn falls_back_to_english() {
+        let exit = SeoError::CliExitError { exit_code: Some(1), stderr: "boom".to_string() };
+        assert_eq!(localize(&exit, "de"), "boom");
+
+        let timeout = SeoError::Timeout { secs: 5 };
+        assert_eq!(localize(&timeout, "xx"), timeout.to_string());
+        assert_eq!(localize(&timeout, ""), timeout.to_string());
+    }
+}
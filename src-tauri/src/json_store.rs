@@ -0,0 +1,63 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::Path;
+
+/// Reads the JSON in `path`, or `T::default()` when there's no such file.
+///
+/// A file that doesn't parse is moved aside to `<name>.bad` and treated as
+/// missing, so a corrupt file can't keep the app from starting.
+pub fn load<T: DeserializeOwned + Default>(path: &Path) -> Result<T, String> {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(T::default()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+
+    Ok(serde_json::from_str(&json).unwrap_or_else(|e| {
+        let mut bad = path.as_os_str().to_owned();
+        bad.push(".bad");
+        log::warn!(
+            "Failed to read {}: {}; moving it to {} and starting empty",
+            path.display(),
+            e,
+            Path::new(&bad).display()
+        );
+        if let Err(e) = std::fs::rename(path, &bad) {
+            log::warn!("Failed to move {} aside: {}", path.display(), e);
+        }
+        T::default()
+    }))
+}
+
+/// Writes `value` to a temp file next to `path` and moves it into place, so
+/// a crash mid-write can't leave a truncated file.
+pub fn save<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<(), String> {
+    let to_error = |e: std::io::Error| format!("Failed to save {}: {}", path.display(), e);
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    std::fs::create_dir_all(dir).map_err(to_error)?;
+    let mut file = tempfile::NamedTempFile::new_in(dir).map_err(to_error)?;
+    serde_json::to_writer_pretty(&mut file, value).map_err(|e| to_error(e.into()))?;
+    file.persist(path).map_err(|e| to_error(e.error))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_corrupt_files_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("items.json");
+        assert_eq!(load::<Vec<String>>(&path).unwrap(), Vec::<String>::new());
+
+        save(&path, &["a", "b"]).unwrap();
+        assert_eq!(load::<Vec<String>>(&path).unwrap(), ["a", "b"]);
+
+        std::fs::write(&path, "[\"a\",").unwrap();
+        assert!(load::<Vec<String>>(&path).unwrap().is_empty());
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_to_string(dir.path().join("items.json.bad")).unwrap(), "[\"a\",");
+    }
+}
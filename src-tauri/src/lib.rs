@@ -1,81 +1,729 @@
+mod batch;
+mod cache;
+mod checks;
+mod cli;
+mod config;
+mod diff;
+mod environment;
+mod error;
+mod estimate;
+mod export;
+mod history;
+mod http;
+mod i18n;
+mod json_store;
+mod logging;
+mod mock;
+mod output;
+mod preflight;
+mod presets;
+mod progress;
+mod queue;
+mod process;
+mod report;
+mod reset;
+mod schedule;
+mod self_test;
+mod sitemap;
+mod source;
+mod validate;
+mod watch;
+mod webhook;
+
+use cache::ResultCache;
+use config::CliConfig;
+use environment::EnvironmentCache;
+use error::SeoError;
+use history::HistoryStore;
+use output::OutputLimit;
+use process::ProcessRegistry;
+use report::{SeoReport, Severity};
 use serde::{Deserialize, Serialize};
-use std::process::Command;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, RunEvent, State};
+use tokio_util::sync::CancellationToken;
+use webhook::{Webhook, WebhookFormat};
+
+/// How long a single SEO check may run before the CLI process is killed.
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+/// How long app exit waits for cancelled checks to kill their CLI processes.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(2);
+
+/// Wait before the first retry of a check that failed with a network error;
+/// each further retry doubles it, up to `MAX_RETRY_DELAY`.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+/// Source of ids for checks started without an explicit `check_id`.
+static NEXT_CHECK_ID: AtomicU64 = AtomicU64::new(1);
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct SeoCheckResult {
     success: bool,
-    data: Option<serde_json::Value>,
+    data: Option<SeoReport>,
     error: Option<String>,
+    /// Typed form of `error`, when the failure has a known cause.
+    error_kind: Option<SeoError>,
+    /// The CLI's exit code when it ran and failed; `None` on success or when
+    /// the CLI never exited on its own.
+    exit_code: Option<i32>,
+    /// What would have run, for a dry run.
+    dry_run: Option<cli::DryRun>,
+    /// Non-fatal messages the CLI wrote to stderr during a successful run.
+    warnings: Vec<String>,
+    /// The CLI failed but printed a parseable report first, which `data`
+    /// holds; checks that hadn't run yet are missing from it.
+    partial: bool,
+    /// The redirects the pre-flight request followed to the page, as each
+    /// redirecting URL and its status.
+    redirect_chain: Vec<(String, u16)>,
+    /// Where each of the requested `formats` was written.
+    artifacts: BTreeMap<String, String>,
+}
+
+impl SeoCheckResult {
+    fn failure(error: SeoError) -> Self {
+        let exit_code = match &error {
+            SeoError::CliExitError { exit_code, .. } => *exit_code,
+            _ => None,
+        };
+        Self {
+            success: false,
+            error: Some(error.to_string()),
+            error_kind: Some(error),
+            exit_code,
+            ..Self::default()
+        }
+    }
+
+    /// A failure for a check that never reached `execute_check`, with its
+    /// `error` in the user's locale like every other failure.
+    fn localized_failure(app: &AppHandle, error: SeoError) -> Self {
+        i18n::localize_result(Self::failure(error), &app.state::<i18n::LocaleState>().get())
+    }
+}
+
+/// Payload of the `seo-check-progress` event, one per line of CLI output.
+#[derive(Debug, Clone, Serialize)]
+struct ProgressEvent {
+    check_id: String,
+    line: String,
+    /// Parsed from `line`; `None` when it doesn't report progress.
+    progress: Option<progress::Progress>,
+}
+
+/// Payload of the `seo-check-retry` event, sent before each retry of a check.
+#[derive(Debug, Clone, Serialize)]
+struct RetryEvent {
+    check_id: String,
+    /// 1-based number of the retry about to run.
+    attempt: u32,
+    max_retries: u32,
+    delay_ms: u64,
+    /// Why the previous attempt failed.
+    error: String,
+}
+
+/// Payload of the `seo-check-complete` event, sent once per check when its
+/// result is ready.
+#[derive(Debug, Clone, Serialize)]
+struct CompleteEvent {
+    check_id: String,
+    /// The URL as requested, before normalization.
+    url: String,
+    /// Wall time of the check, including any retries.
+    duration_ms: u64,
+    score: Option<f64>,
+    success: bool,
+}
+
+/// Everything needed to run one SEO check.
+#[derive(Debug, Clone, Default)]
+struct CheckRequest {
+    url: String,
+    /// Preset id, config file path, or inline config object.
+    config: Option<serde_json::Value>,
+    timeout_secs: Option<u64>,
+    check_id: Option<String>,
+    /// Skip the result cache and always run the CLI.
+    force_refresh: bool,
+    /// How old a cached result may be and still be reused.
+    cache_ttl_secs: Option<u64>,
+    /// How many times to rerun the CLI after a network failure.
+    retries: u32,
+    user_agent: Option<String>,
+    /// Extra HTTP headers, sorted so the CLI arguments are stable.
+    headers: BTreeMap<String, String>,
+    /// Proxy URL for the browser's traffic.
+    proxy: Option<String>,
+    /// Extra environment variables for the CLI process.
+    env: BTreeMap<String, String>,
+    /// Allow `env` to override variables such as `PATH`.
+    allow_protected_env: bool,
+    /// Return the command that would run instead of running it.
+    dry_run: bool,
+    /// Cap on each of the CLI's stdout and stderr, in bytes.
+    max_output_bytes: Option<usize>,
+    /// HTTP basic auth username and password.
+    basic_auth: Option<(String, String)>,
+    /// Keep the result out of history and the cache, for internal checks.
+    ephemeral: bool,
+    /// `url` is a validated `file://` URL from `run_seo_check_file`.
+    local_file: bool,
+    /// Make sure the page answers before starting the CLI.
+    preflight: bool,
+    /// Raw CLI arguments passed after the managed ones.
+    extra_args: Vec<String>,
+    /// Allow `extra_args` to include flags such as `--output`.
+    allow_unsafe_args: bool,
+}
+
+impl CheckRequest {
+    /// Whether results may be served from and stored in the cache, which is
+    /// keyed only by URL and config. A custom user agent or headers can change
+    /// what the site serves, and environment variables what the CLI does.
+    /// Credentials aren't part of the key either, nor are extra CLI
+    /// arguments. A dry run has no result to cache, and a local file is
+    /// usually being edited between checks.
+    fn is_cacheable(&self) -> bool {
+        self.user_agent.is_none()
+            && self.headers.is_empty()
+            && self.extra_args.is_empty()
+            && self.env.is_empty()
+            && self.basic_auth.is_none()
+            && !self.dry_run
+            && !self.ephemeral
+            && !self.local_file
+    }
 }
 
 #[tauri::command]
-async fn run_seo_check(url: String, config: Option<String>) -> Result<SeoCheckResult, String> {
+#[allow(clippy::too_many_arguments)]
+async fn run_seo_check(
+    app: AppHandle,
+    url: String,
+    config: Option<serde_json::Value>,
+    timeout_secs: Option<u64>,
+    check_id: Option<String>,
+    force_refresh: Option<bool>,
+    cache_ttl_secs: Option<u64>,
+    retries: Option<u32>,
+    user_agent: Option<String>,
+    headers: Option<HashMap<String, String>>,
+    proxy: Option<String>,
+    env: Option<HashMap<String, String>>,
+    allow_protected_env: Option<bool>,
+    webhook_url: Option<String>,
+    webhook_format: Option<WebhookFormat>,
+    dry_run: Option<bool>,
+    max_output_bytes: Option<usize>,
+    basic_auth: Option<(String, String)>,
+    output_file: Option<String>,
+    min_severity: Option<String>,
+    preflight: Option<bool>,
+    formats: Option<Vec<String>>,
+    report_dir: Option<String>,
+    extra_args: Option<Vec<String>>,
+    allow_unsafe_args: Option<bool>,
+) -> Result<SeoCheckResult, String> {
+    let invalid = |message| SeoCheckResult::localized_failure(&app, SeoError::InvalidArgument { message });
+    let webhook = match webhook_url
+        .map(|webhook_url| Webhook::new(&webhook_url, webhook_format.unwrap_or_default()))
+        .transpose()
+    {
+        Ok(webhook) => webhook,
+        Err(message) => return Ok(invalid(message)),
+    };
+    let min_severity = match min_severity.as_deref().map(str::parse::<Severity>).transpose() {
+        Ok(min_severity) => min_severity,
+        Err(message) => return Ok(invalid(message)),
+    };
+    // Fail before a long audit rather than after it
+    let output_file = output_file.map(PathBuf::from);
+    if let Some(path) = &output_file {
+        if let Err(message) = export::prepare_output_path(path) {
+            return Ok(invalid(message));
+        }
+    }
+    let formats = formats.unwrap_or_default();
+    if let Err(message) = formats.iter().try_for_each(|format| export::find_format(format).map(drop)) {
+        return Ok(invalid(message));
+    }
+    let report_dir = match report_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => app
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to find the app data directory: {}", e))?
+            .join("reports"),
+    };
+
+    let requested_url = url.clone();
+    let dry_run = dry_run.unwrap_or(false);
+    let mut result = execute_check(
+        &app,
+        CheckRequest {
+            url,
+            config,
+            timeout_secs,
+            check_id,
+            force_refresh: force_refresh.unwrap_or(false),
+            cache_ttl_secs,
+            retries: retries.unwrap_or(0),
+            user_agent,
+            headers: headers.unwrap_or_default().into_iter().collect(),
+            proxy,
+            env: env.unwrap_or_default().into_iter().collect(),
+            allow_protected_env: allow_protected_env.unwrap_or(false),
+            dry_run,
+            max_output_bytes,
+            basic_auth,
+            ephemeral: false,
+            local_file: false,
+            preflight: preflight.unwrap_or(true),
+            extra_args: extra_args.unwrap_or_default(),
+            allow_unsafe_args: allow_unsafe_args.unwrap_or(false),
+        },
+    )
+    .await?;
+
+    // Failed checks only have output to save when it was partial
+    if let (Some(path), Some(report)) = (&output_file, &result.data) {
+        export::write_json(&report.raw, path)?;
+    }
+    if let (false, Some(report)) = (formats.is_empty(), &result.data) {
+        let formats: Vec<&str> = formats.iter().map(String::as_str).collect();
+        result.artifacts = export::write_artifacts(report, &formats, &report_dir)?;
+    }
+    if let Some(webhook) = webhook.filter(|_| !dry_run) {
+        webhook.send_result(&requested_url, &result).await;
+    }
+    if let (Some(min), Some(report)) = (min_severity, &mut result.data) {
+        report.retain_severity(min);
+    }
+    Ok(result)
+}
+
+/// Audits a local `.html` or `.htm` file, e.g. a page that isn't deployed
+/// yet, by loading it through a `file://` URL.
+#[tauri::command]
+async fn run_seo_check_file(
+    app: AppHandle,
+    file_path: String,
+    config: Option<serde_json::Value>,
+) -> Result<SeoCheckResult, String> {
+    let url = match validate::html_file(&file_path) {
+        Ok(url) => url,
+        Err(message) => {
+            return Ok(SeoCheckResult::localized_failure(&app, SeoError::InvalidArgument { message }));
+        }
+    };
+    execute_check(
+        &app,
+        CheckRequest {
+            url: url.into(),
+            config,
+            local_file: true,
+            ..CheckRequest::default()
+        },
+    )
+    .await
+}
+
+/// Runs a single SEO check, shared by the single-URL and batch commands.
+///
+/// Failures are reported through the returned result rather than an `Err`,
+/// so callers always get something to show for the URL.
+async fn execute_check(app: &AppHandle, request: CheckRequest) -> Result<SeoCheckResult, String> {
+    // Every attempt runs under the same id so it can be cancelled throughout
+    let check_id = request
+        .check_id
+        .clone()
+        .unwrap_or_else(|| format!("check-{}", NEXT_CHECK_ID.fetch_add(1, Ordering::Relaxed)));
+
+    let started = Instant::now();
+    let result = match app.state::<ProcessRegistry>().register(&check_id) {
+        Ok(registration) => retry_check(app, &request, &check_id, registration.token()).await,
+        Err(message) => SeoCheckResult::failure(SeoError::SpawnFailed { message }),
+    };
+    let result = i18n::localize_result(result, &app.state::<i18n::LocaleState>().get());
+
+    let _ = app.emit(
+        "seo-check-complete",
+        CompleteEvent {
+            check_id,
+            url: request.url,
+            duration_ms: started.elapsed().as_millis() as u64,
+            score: result
+                .data
+                .as_ref()
+                .filter(|_| result.success)
+                .and_then(|report| report.score)
+                .map(report::Score::percent),
+            success: result.success,
+        },
+    );
+    Ok(result)
+}
+
+/// Runs `try_check`, retrying network failures up to `request.retries` times
+/// with exponential backoff. A partial result is retried like any other
+/// failure, and returned if it was the last attempt. Cancelling `token`
+/// also cancels a pending retry.
+async fn retry_check(
+    app: &AppHandle,
+    request: &CheckRequest,
+    check_id: &str,
+    token: &CancellationToken,
+) -> SeoCheckResult {
+    let mut attempt = 0;
+    loop {
+        let retryable = |e: &SeoError| attempt < request.retries && e.is_transient();
+        let error = match try_check(app, request, check_id, token).await {
+            Ok(result) if result.partial => match result.error_kind.clone() {
+                Some(e) if retryable(&e) => e,
+                _ => return result,
+            },
+            Ok(result) => return result,
+            Err(e) if retryable(&e) => e,
+            Err(e) => return SeoCheckResult::failure(e),
+        };
+
+        attempt += 1;
+        let delay = retry_delay(attempt);
+        log::info!("Retrying SEO check {} ({}/{}): {}", check_id, attempt, request.retries, error);
+        let _ = app.emit(
+            "seo-check-retry",
+            RetryEvent {
+                check_id: check_id.to_string(),
+                attempt,
+                max_retries: request.retries,
+                delay_ms: delay.as_millis() as u64,
+                error: error.to_string(),
+            },
+        );
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = token.cancelled() => return SeoCheckResult::failure(SeoError::Cancelled),
+        }
+    }
+}
+
+/// Backoff before the `attempt`th retry (1-based).
+fn retry_delay(attempt: u32) -> Duration {
+    INITIAL_RETRY_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(MAX_RETRY_DELAY)
+}
+
+async fn try_check(
+    app: &AppHandle,
+    request: &CheckRequest,
+    check_id: &str,
+    token: &CancellationToken,
+) -> Result<SeoCheckResult, SeoError> {
+    let timeout_secs = request.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS);
+
+    let url = if request.local_file {
+        url::Url::parse(&request.url).map_err(|e| e.to_string())
+    } else {
+        validate::normalize_url(&request.url)
+    }
+    .map_err(|message| SeoError::InvalidUrl { message })?;
+
+    // Mock results replace real ones, cached or not
+    if mock::enabled() && !request.dry_run {
+        return mock::check(app, check_id, &url, token).await;
+    }
+
+    let cache = app.state::<ResultCache>();
+    let config_value = request.config.clone().unwrap_or_default();
+    let config_key = config::config_key(&config_value);
+    let config = config_key.as_deref();
+    let saved_presets = app.state::<presets::PresetStore>().list();
+    let cache_key = config::cache_key(&config_value, &saved_presets);
+    if !request.force_refresh && request.is_cacheable() {
+        let ttl = request
+            .cache_ttl_secs
+            .map_or(cache::DEFAULT_CACHE_TTL, Duration::from_secs);
+        if let Some(cached) = cache.get(url.as_str(), cache_key.as_deref(), ttl) {
+            return Ok(cached);
+        }
+    }
+
+    let preflight = if request.preflight && !request.dry_run && !request.local_file {
+        tokio::select! {
+            checked = preflight::check(request, &url) => checked?,
+            _ = token.cancelled() => return Err(SeoError::Cancelled),
+        }
+    } else {
+        preflight::Preflight::default()
+    };
+
+    // An inline config's temp file lives until this function returns
+    let cli_config = CliConfig::from_value(&config_value, &saved_presets)?;
+    let version = cli::cli_version(app).await?;
+    let args = cli::check_args(request, &url, cli_config.as_ref(), &version)?;
+
     // Build the command to run the SEO checker
-    let mut cmd = Command::new("node");
+    let mut cmd = cli::cli_command(app)?;
+    cmd.args(args);
+    cli::apply_env(&mut cmd, &request.env, request.allow_protected_env)?;
+
+    if request.dry_run {
+        return Ok(SeoCheckResult {
+            success: true,
+            dry_run: Some(cli::DryRun::describe(&cmd)),
+            ..SeoCheckResult::default()
+        });
+    }
+
+    // Cancelled while the CLI version was being queried
+    if token.is_cancelled() {
+        return Err(SeoError::Cancelled);
+    }
+
+    // Execute the command, killing it if it's cancelled or doesn't finish in time
+    cmd.stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
 
-    // Get the app directory to find the CLI
-    let app_dir = std::env::current_dir()
-        .map_err(|e| format!("Failed to get current directory: {}", e))?;
+    let invocation = cli::DryRun::describe(&cmd);
+    let working_dir = cmd
+        .as_std()
+        .get_current_dir()
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default();
+    log::debug!(
+        "Running SEO check {}: {:?} in {} with env {:?}",
+        check_id,
+        invocation.command,
+        working_dir.display(),
+        invocation.env
+    );
 
-    let cli_path = app_dir.join("dist").join("cli.js");
+    let started = Instant::now();
+    let mut child = cmd.spawn().map_err(cli::spawn_error)?;
 
-    cmd.arg(cli_path.to_str().unwrap())
-        .arg(&url)
-        .arg("--json");
+    // Drain both pipes in the background so the CLI never blocks on a full
+    // pipe, emitting what it prints before the report as progress. Going
+    // over the output cap cancels this attempt like `cancel_seo_check` would.
+    let attempt = token.child_token();
+    let limit = OutputLimit::new(
+        request.max_output_bytes.unwrap_or(output::DEFAULT_MAX_OUTPUT_BYTES),
+        attempt.clone(),
+    );
+    let stdout_task = tokio::spawn({
+        let (app, check_id, limit) = (app.clone(), check_id.to_string(), limit.clone());
+        let stdout = child.stdout.take();
+        async move {
+            let pattern = app.state::<progress::ProgressPattern>().get();
+            output::read_report(stdout, &limit, |line| {
+                let _ = app.emit(
+                    "seo-check-progress",
+                    ProgressEvent {
+                        check_id: check_id.clone(),
+                        progress: progress::parse(&pattern, &line),
+                        line,
+                    },
+                );
+            })
+            .await
+        }
+    });
+    let stderr_task = tokio::spawn({
+        let (stderr, limit) = (child.stderr.take(), limit.clone());
+        async move { output::read_all(stderr, &limit).await }
+    });
 
-    // Add config if provided
-    if let Some(cfg) = config {
-        cmd.arg("--config").arg(cfg);
+    let status = tokio::select! {
+        status = child.wait() => status.map_err(|e| SeoError::SpawnFailed {
+            message: format!("Failed to wait for SEO checker: {}", e),
+        })?,
+        _ = attempt.cancelled() => {
+            let _ = child.kill().await;
+            log::debug!("SEO check {} killed after {} ms", check_id, started.elapsed().as_millis());
+            return Err(if limit.exceeded() {
+                SeoError::OutputTooLarge {
+                    limit_bytes: limit.max_bytes(),
+                }
+            } else {
+                SeoError::Cancelled
+            });
+        }
+        _ = tokio::time::sleep(Duration::from_secs(timeout_secs)) => {
+            let _ = child.kill().await;
+            log::debug!("SEO check {} timed out after {} ms", check_id, started.elapsed().as_millis());
+            return Err(SeoError::Timeout { secs: timeout_secs });
+        }
+    };
+
+    log::debug!(
+        "SEO check {} exited with code {:?} after {} ms",
+        check_id,
+        status.code(),
+        started.elapsed().as_millis()
+    );
+
+    let stdout = stdout_task.await.unwrap_or_default();
+    let stderr = stderr_task.await.unwrap_or_default();
+    // The CLI may have exited on its own once its pipe was closed
+    if limit.exceeded() {
+        return Err(SeoError::OutputTooLarge {
+            limit_bytes: limit.max_bytes(),
+        });
     }
 
-    // Execute the command
-    let output = cmd.output()
-        .map_err(|e| format!("Failed to execute SEO checker: {}", e))?;
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    if status.success() {
+        let mut data = SeoReport::from_cli_output(stdout)?;
+        data.set_total_time(elapsed_ms);
 
-    if output.status.success() {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let data: serde_json::Value = serde_json::from_str(&stdout)
-            .map_err(|e| format!("Failed to parse JSON output: {}", e))?;
+        // History is a convenience; a failed write shouldn't fail the check
+        if !request.ephemeral {
+            if let Err(e) = app.state::<HistoryStore>().record(url.as_str(), config, &data, elapsed_ms) {
+                log::warn!("{}", e);
+            }
+        }
 
-        Ok(SeoCheckResult {
+        let mut warnings = cli::warnings(&String::from_utf8_lossy(&stderr));
+        warnings.splice(0..0, preflight.warning);
+        let result = SeoCheckResult {
             success: true,
             data: Some(data),
-            error: None,
-        })
+            warnings,
+            redirect_chain: preflight.redirect_chain,
+            ..SeoCheckResult::default()
+        };
+        if request.is_cacheable() {
+            cache.insert(url.as_str(), cache_key.as_deref(), result.clone());
+        }
+        Ok(result)
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Ok(SeoCheckResult {
-            success: false,
-            data: None,
-            error: Some(stderr.to_string()),
-        })
+        let error = SeoError::CliExitError {
+            exit_code: status.code(),
+            stderr: String::from_utf8_lossy(&stderr).to_string(),
+        };
+        // A crash late in the run can still leave a complete report of the
+        // checks that finished; it's neither recorded nor cached
+        match SeoReport::from_cli_output(stdout) {
+            Ok(mut partial) => {
+                partial.set_total_time(elapsed_ms);
+                Ok(SeoCheckResult {
+                    data: Some(partial),
+                    partial: true,
+                    redirect_chain: preflight.redirect_chain,
+                    ..SeoCheckResult::failure(error)
+                })
+            }
+            Err(_) => Err(error),
+        }
     }
 }
 
 #[tauri::command]
-async fn get_available_presets() -> Result<Vec<String>, String> {
-    Ok(vec![
-        "basic".to_string(),
-        "advanced".to_string(),
-        "strict".to_string(),
-    ])
+async fn cancel_seo_check(
+    check_id: String,
+    registry: State<'_, ProcessRegistry>,
+) -> Result<(), String> {
+    registry.cancel(&check_id)
+}
+
+/// Cancels every running check, single, batched or scheduled, along with the
+/// checks batches haven't started yet. Returns how many were running.
+#[tauri::command]
+async fn cancel_all_checks(registry: State<'_, ProcessRegistry>) -> Result<usize, String> {
+    let cancelled = registry.cancel_running();
+    log::info!("Cancelled {} running SEO checks", cancelled);
+    Ok(cancelled)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
   tauri::Builder::default()
     .plugin(tauri_plugin_shell::init())
+    .manage(ProcessRegistry::default())
+    .manage(ResultCache::default())
+    .manage(EnvironmentCache::default())
+    .manage(environment::NodePath::default())
+    .manage(cli::CliVersionCache::default())
+    .manage(i18n::LocaleState::default())
+    .manage(progress::ProgressPattern::default())
+    .manage(watch::WatchRegistry::default())
+    .manage(queue::JobQueue::default())
     .setup(|app| {
-      if cfg!(debug_assertions) {
-        app.handle().plugin(
-          tauri_plugin_log::Builder::default()
-            .level(log::LevelFilter::Info)
-            .build(),
-        )?;
-      }
+      logging::init(app.handle())?;
+      let data_dir = app.path().app_data_dir()?;
+      app.manage(HistoryStore::open(&data_dir)?);
+      app.manage(schedule::ScheduleStore::open(&data_dir)?);
+      app.manage(presets::PresetStore::open(&app.path().app_config_dir()?)?);
+      environment::probe_on_startup(app.handle());
+      schedule::start(app.handle());
       Ok(())
     })
-    .invoke_handler(tauri::generate_handler![run_seo_check, get_available_presets])
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+    .invoke_handler(tauri::generate_handler![
+      run_seo_check,
+      run_seo_check_file,
+      batch::run_seo_checks,
+      estimate::estimate_audit,
+      queue::enqueue_check,
+      queue::get_queue_status,
+      sitemap::run_sitemap_check,
+      source::fetch_page_source,
+      cli::get_cli_version,
+      i18n::set_locale,
+      logging::set_log_level,
+      progress::set_progress_pattern,
+      cancel_seo_check,
+      cancel_all_checks,
+      cache::clear_seo_cache,
+      reset::reset_app_data,
+      history::get_check_history,
+      history::get_history_stats,
+      history::delete_history_entry,
+      schedule::schedule_check,
+      schedule::cancel_schedule,
+      schedule::list_schedules,
+      diff::diff_reports,
+      presets::get_available_presets,
+      checks::describe_checks,
+      presets::save_preset,
+      presets::delete_preset,
+      presets::verify_preset,
+      config::validate_config,
+      environment::check_environment,
+      environment::set_node_path,
+      self_test::self_test,
+      export::export_report,
+      export::format_summary,
+      watch::watch_file,
+      watch::stop_watch
+    ])
+    .build(tauri::generate_context!())
+    .expect("error while building tauri application")
+    .run(|app, event| {
+      if let RunEvent::ExitRequested { .. } = event {
+        shut_down(app);
+      }
+    });
+}
+
+/// Stops scheduled checks and kills every running CLI process, so quitting
+/// mid-check doesn't leave node processes behind.
+fn shut_down(app: &AppHandle) {
+    app.state::<schedule::ScheduleStore>().stop();
+    app.state::<watch::WatchRegistry>().stop_all();
+    let registry = app.state::<ProcessRegistry>();
+    let cancelled = registry.cancel_all();
+    if registry.wait_idle(SHUTDOWN_GRACE) {
+        log::info!("Shutting down: cancelled {} running SEO check(s)", cancelled);
+    } else {
+        log::warn!("Shutting down: cancelled {} SEO check(s), but not all stopped in time", cancelled);
+    }
 }
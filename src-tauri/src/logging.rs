@@ -0,0 +1,67 @@
+use log::LevelFilter;
+use tauri::plugin::TauriPlugin;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind};
+
+/// Level used until the frontend calls `set_log_level`.
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::Info;
+
+/// Size at which the log file is rotated, and how many old files are kept.
+const MAX_LOG_FILE_BYTES: u128 = 5_000_000;
+const KEPT_LOG_FILES: usize = 3;
+
+/// The log plugin, writing to stdout and to a file in the app's log
+/// directory in every build, so users can send logs from release builds.
+///
+/// It passes every level on; `log::set_max_level` decides what is recorded,
+/// which unlike the plugin's own level can change at runtime.
+fn plugin<R: Runtime>() -> TauriPlugin<R> {
+    tauri_plugin_log::Builder::default()
+        .level(LevelFilter::Trace)
+        .targets([
+            Target::new(TargetKind::Stdout),
+            Target::new(TargetKind::LogDir { file_name: None }),
+        ])
+        .max_file_size(MAX_LOG_FILE_BYTES)
+        .rotation_strategy(RotationStrategy::KeepSome(KEPT_LOG_FILES))
+        .build()
+}
+
+/// Installs the log plugin at `DEFAULT_LEVEL`.
+pub fn init<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    app.plugin(plugin())?;
+    log::set_max_level(DEFAULT_LEVEL);
+    Ok(())
+}
+
+/// Parses `off`, `error`, `warn`, `info`, `debug` or `trace`, in any case.
+fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    level.trim().parse().map_err(|_| {
+        format!(
+            "Unknown log level: {}. Use off, error, warn, info, debug or trace",
+            level
+        )
+    })
+}
+
+#[tauri::command]
+pub async fn set_log_level(level: String) -> Result<(), String> {
+    let level = parse_level(&level)?;
+    log::set_max_level(level);
+    log::info!("Log level set to {}", level);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_standard_level_names() {
+        assert_eq!(parse_level("debug").unwrap(), LevelFilter::Debug);
+        assert_eq!(parse_level(" WARN ").unwrap(), LevelFilter::Warn);
+        assert_eq!(parse_level("off").unwrap(), LevelFilter::Off);
+        assert!(parse_level("verbose").unwrap_err().contains("Unknown log level: verbose"));
+        assert!(parse_level("").is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use crate::error::SeoError;
+use crate::report::SeoReport;
+use crate::{progress, ProgressEvent, SeoCheckResult};
+use serde_json::json;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio_util::sync::CancellationToken;
+use url::Url;
+
+/// Set to `1` in a debug build to answer checks with `report` instead of
+/// running the CLI, e.g. to work on the frontend without Node.js.
+const MOCK_VAR: &str = "SEO_MOCK";
+
+/// How long a mock check takes, in milliseconds.
+const DELAY_VAR: &str = "SEO_MOCK_DELAY_MS";
+const DEFAULT_DELAY: Duration = Duration::from_millis(1500);
+
+/// Whether checks are mocked. Never in release builds, whatever the
+/// environment says.
+pub fn enabled() -> bool {
+    cfg!(debug_assertions) && std::env::var(MOCK_VAR).is_ok_and(|value| value == "1")
+}
+
+fn delay() -> Duration {
+    std::env::var(DELAY_VAR)
+        .ok()
+        .and_then(|ms| ms.trim().parse().ok())
+        .map_or(DEFAULT_DELAY, Duration::from_millis)
+}
+
+/// A report for `url` with passed, warning, error and info results, in the
+/// shape the CLI prints.
+fn report(url: &Url) -> serde_json::Value {
+    json!({
+        "url": url.as_str(),
+        "timestamp": "2024-01-01T00:00:00.000Z",
+        "score": 72,
+        "summary": { "total": 9, "passed": 5, "failed": 4 },
+        "checks": {
+            "metaTags": [
+                { "passed": true, "message": "Title exists (42 characters)", "details": { "length": 42 } },
+                { "passed": false, "message": "Meta description is missing", "severity": "error" },
+                { "passed": true, "message": "Viewport meta tag is set" }
+            ],
+            "headings": [
+                { "passed": true, "message": "Page has exactly one H1" },
+                { "passed": false, "message": "Heading levels skip from H2 to H4", "severity": "warning" }
+            ],
+            "images": [
+                {
+                    "passed": false,
+                    "message": "2 images are missing alt text",
+                    "severity": "warning",
+                    "details": { "images": ["/hero.jpg", "/logo.png"] }
+                },
+                { "passed": true, "message": "Images use modern formats" }
+            ],
+            "security": [
+                { "passed": true, "message": "Page is served over HTTPS" },
+                { "passed": false, "message": "No Content-Security-Policy header", "severity": "info" }
+            ]
+        }
+    })
+}
+
+/// Answers a check with `report` after `SEO_MOCK_DELAY_MS`, emitting a
+/// progress line per checker along the way like the CLI does. The result
+/// isn't recorded in history or cached.
+pub async fn check(
+    app: &AppHandle,
+    check_id: &str,
+    url: &Url,
+    token: &CancellationToken,
+) -> Result<SeoCheckResult, SeoError> {
+    let raw = report(url);
+    let checkers: Vec<&String> = raw["checks"].as_object().into_iter().flat_map(|checks| checks.keys()).collect();
+    let delay = delay();
+    let step = delay / checkers.len().max(1) as u32;
+    log::info!("Mocking SEO check {} of {} (SEO_MOCK=1)", check_id, url);
+
+    let pattern = app.state::<progress::ProgressPattern>().get();
+    for (index, checker) in checkers.iter().enumerate() {
+        let line = format!("Checking {}/{}: {}", index + 1, checkers.len(), checker);
+        let _ = app.emit(
+            "seo-check-progress",
+            ProgressEvent {
+                check_id: check_id.to_string(),
+                progress: progress::parse(&pattern, &line),
+                line,
+            },
+        );
+        tokio::select! {
+            _ = tokio::time::sleep(step) => {}
+            _ = token.cancelled() => return Err(SeoError::Cancelled),
+        }
+    }
+
+    let mut data = SeoReport::from_cli_output(raw.to_string().into_bytes())?;
+    data.set_total_time(delay.as_millis() as u64);
+    Ok(SeoCheckResult {
+        success: true,
+        data: Some(data),
+        warnings: vec!["Mock result: SEO_MOCK=1 is set, so the CLI didn't run".to_string()],
+        ..SeoCheckResult::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Severity;
+
+    #[test]
+    fn mock_report_matches_the_cli_schema() {
+        let url = Url::parse("https://example.com/").unwrap();
+        let report = SeoReport::from_cli_output(report(&url).to_string().into_bytes()).unwrap();
+        assert_eq!(report.url, "https://example.com/");
+        assert_eq!(report.grade.as_deref(), Some("C"));
+
+        let checks: Vec<_> = report.checks.values().flatten().collect();
+        let failed: Vec<_> = checks.iter().filter(|check| !check.passed).map(|check| check.severity).collect();
+        assert_eq!(report.summary.total as usize, checks.len());
+        assert_eq!(report.summary.failed as usize, failed.len());
+        for severity in [Severity::Error, Severity::Warning, Severity::Info] {
+            assert!(failed.contains(&Some(severity)), "no failed {:?} check", severity);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, BufReader};
+use tokio_util::sync::CancellationToken;
+
+/// How much a check's CLI may write to each of stdout and stderr.
+pub const DEFAULT_MAX_OUTPUT_BYTES: usize = 50 * 1024 * 1024;
+
+/// Caps how much of each CLI pipe is read, so a runaway audit can't exhaust
+/// memory. Clones share the flag recording that a pipe went over the cap.
+#[derive(Debug, Clone)]
+pub struct OutputLimit {
+    max_bytes: usize,
+    exceeded: Arc<AtomicBool>,
+    /// Cancelled when a pipe goes over the cap, to stop the run.
+    on_exceeded: CancellationToken,
+}
+
+impl OutputLimit {
+    pub fn new(max_bytes: usize, on_exceeded: CancellationToken) -> Self {
+        Self {
+            max_bytes,
+            exceeded: Arc::default(),
+            on_exceeded,
+        }
+    }
+
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Whether a reader stopped because its pipe went over the cap.
+    pub fn exceeded(&self) -> bool {
+        self.exceeded.load(Ordering::SeqCst)
+    }
+
+    /// Records `read` bytes read so far, returning false once over the cap.
+    fn allows(&self, read: usize) -> bool {
+        if read > self.max_bytes {
+            self.exceeded.store(true, Ordering::SeqCst);
+            self.on_exceeded.cancel();
+            return false;
+        }
+        true
+    }
+
+    /// How many more bytes to read at most; one past the cap is enough to
+    /// tell that it was exceeded.
+    fn remaining(&self, read: usize) -> u64 {
+        (self.max_bytes + 1).saturating_sub(read) as u64
+    }
+}
+
+/// Reads a child pipe to the end, returning whatever was read before an
+/// error or before the pipe went over `limit`.
+pub async fn read_all<R: AsyncRead + Unpin>(pipe: Option<R>, limit: &OutputLimit) -> Vec<u8> {
+    let mut buf = Vec::new();
+    if let Some(pipe) = pipe {
+        let _ = pipe.take(limit.remaining(0)).read_to_end(&mut buf).await;
+        limit.allows(buf.len());
+    }
+    buf
+}
+
+/// Passes each line of CLI output to `on_progress` until the JSON report
+/// starts, then returns the report bytes. Reading stops early if stdout goes
+/// over `limit`.
+///
+/// The report is everything from the first line that opens with `{`, so
+/// progress text printed before it must not start with a brace.
+pub async fn read_report<R: AsyncRead + Unpin>(
+    pipe: Option<R>,
+    limit: &OutputLimit,
+    mut on_progress: impl FnMut(String),
+) -> Vec<u8> {
+    let mut json = Vec::new();
+    let Some(pipe) = pipe else {
+        return json;
+    };
+
+    let mut reader = BufReader::new(pipe);
+    let mut line = Vec::new();
+    let mut read = 0;
+    loop {
+        line.clear();
+        // Bounded, so one endless line can't get past the cap either
+        match (&mut reader).take(limit.remaining(read)).read_until(b'\n', &mut line).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => read += n,
+        }
+        if !limit.allows(read) {
+            break;
+        }
+
+        if !json.is_empty() || line.starts_with(b"{") {
+            json.extend_from_slice(&line);
+            continue;
+        }
+
+        let text = String::from_utf8_lossy(&line).trim_end().to_string();
+        if !text.is_empty() {
+            on_progress(text);
+        }
+    }
+    json
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::process::Stdio;
+    use tokio::process::Command;
+
+    fn flood(script: &str) -> tokio::process::Child {
+        Command::new("sh")
+            .args(["-c", script])
+            .stdout(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn stops_reading_a_flooding_cli_at_the_cap() {
+        let stop = CancellationToken::new();
+        let limit = OutputLimit::new(1024 * 1024, stop.clone());
+        let mut cli = flood("echo 'Checking 1/2 pages'; echo '{'; yes '\"broken link\",'");
+        let mut progress = Vec::new();
+        let report = read_report(cli.stdout.take(), &limit, |line| progress.push(line)).await;
+        assert!(limit.exceeded() && stop.is_cancelled());
+        assert!(report.len() <= limit.max_bytes());
+        assert_eq!(progress, ["Checking 1/2 pages"]);
+
+        let limit = OutputLimit::new(1024 * 1024, CancellationToken::new());
+        let mut cli = flood("yes");
+        assert!(read_all(cli.stdout.take(), &limit).await.len() <= limit.max_bytes() + 1);
+        assert!(limit.exceeded());
+    }
+
+    #[tokio::test]
+    async fn reads_output_under_the_cap_in_full() {
+        let limit = OutputLimit::new(1024, CancellationToken::new());
+        let mut cli = flood("echo 'Launching browser'; echo '{\"score\": 90}'");
+        let report = read_report(cli.stdout.take(), &limit, |_| {}).await;
+        assert_eq!(report, b"{\"score\": 90}\n");
+        assert!(!limit.exceeded());
+    }
+}
@@ -0,0 +1,206 @@
+use crate::error::SeoError;
+use crate::CheckRequest;
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::time::Duration;
+use url::Url;
+
+/// How long the page may take to answer before the check is abandoned, per
+/// request.
+const PREFLIGHT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Most redirects followed, as many as browsers and reqwest allow.
+const MAX_REDIRECTS: usize = 10;
+
+/// Longer redirect chains than this are flagged; each hop costs a round trip
+/// and search engines may stop following them.
+const LONG_CHAIN: usize = 3;
+
+/// What the pre-flight request found on its way to the page.
+#[derive(Debug, Default, PartialEq)]
+pub struct Preflight {
+    /// Each redirect followed: the URL that redirected and its status.
+    pub redirect_chain: Vec<(String, u16)>,
+    /// Set for long redirect chains and loops.
+    pub warning: Option<String>,
+}
+
+/// Makes sure a page answers without an error status before starting the
+/// CLI, which takes seconds to launch a browser only to find a 404.
+///
+/// Sends the check's user agent, headers and proxy, and its credentials to
+/// the original host only. Redirects are followed one at a time so the chain
+/// can be reported. A loop, or more than `MAX_REDIRECTS` redirects, only
+/// gets a warning: without the cookies a browser keeps, a site can redirect
+/// in circles that the CLI's browser doesn't. Servers that don't implement
+/// `HEAD` are asked with `GET` instead.
+pub async fn check(request: &CheckRequest, url: &Url) -> Result<Preflight, SeoError> {
+    let headers: HashMap<String, String> = request.headers.clone().into_iter().collect();
+    let client = crate::http::page_client_builder(
+        PREFLIGHT_TIMEOUT,
+        request.user_agent.as_deref(),
+        &headers,
+        request.proxy.as_deref(),
+    )
+    .and_then(|builder| {
+        builder
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))
+    })
+    .map_err(|message| SeoError::InvalidArgument { message })?;
+
+    let send = |method, target: &Url| {
+        let mut builder = client.request(method, target.clone());
+        if let Some((username, password)) = &request.basic_auth {
+            if target.host_str() == url.host_str() {
+                builder = builder.basic_auth(username, Some(password));
+            }
+        }
+        builder.send()
+    };
+    let unreachable = |e: reqwest::Error| SeoError::Unreachable {
+        status: None,
+        message: e.without_url().to_string(),
+    };
+
+    let mut preflight = Preflight::default();
+    let mut current = url.clone();
+    loop {
+        let mut response = send(reqwest::Method::HEAD, &current).await.map_err(unreachable)?;
+        if matches!(response.status(), StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED) {
+            response = send(reqwest::Method::GET, &current).await.map_err(unreachable)?;
+        }
+
+        let status = response.status();
+        if status.is_client_error() || status.is_server_error() {
+            return Err(SeoError::Unreachable {
+                status: Some(status.as_u16()),
+                message: format!("{} responded with HTTP {}", current, status),
+            });
+        }
+
+        // A redirect without a usable Location is as far as anyone gets
+        let next = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .and_then(|location| current.join(location).ok())
+            .filter(|_| status.is_redirection());
+        let Some(next) = next else {
+            break;
+        };
+
+        preflight.redirect_chain.push((current.to_string(), status.as_u16()));
+        if preflight.redirect_chain.iter().any(|(hop, _)| *hop == next.as_str()) {
+            preflight.warning = Some(format!("Redirect loop: {} redirects back to {}", current, next));
+            break;
+        }
+        if preflight.redirect_chain.len() >= MAX_REDIRECTS {
+            preflight.warning = Some(format!(
+                "Stopped following redirects after {}, at {}",
+                MAX_REDIRECTS, next
+            ));
+            break;
+        }
+        current = next;
+    }
+
+    if preflight.warning.is_none() && preflight.redirect_chain.len() > LONG_CHAIN {
+        preflight.warning = Some(format!(
+            "{} redirects before reaching {}; keep redirect chains to {} or fewer",
+            preflight.redirect_chain.len(),
+            current,
+            LONG_CHAIN
+        ));
+    }
+    Ok(preflight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serves a few paths: `/ok`, `/missing`, `/head-not-allowed` (405 for
+    /// `HEAD`), `/hop/N` redirecting down to `/ok` and `/loop/a` and
+    /// `/loop/b` redirecting to each other.
+    async fn serve() -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0; 1024];
+                let read = stream.read(&mut request).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..read]).into_owned();
+                let mut words = request.split_whitespace();
+                let (method, path) = (words.next().unwrap_or_default(), words.next().unwrap_or_default());
+
+                let hop = path.strip_prefix("/hop/").and_then(|n| n.parse::<u32>().ok());
+                let (status, location) = match (method, path, hop) {
+                    (_, "/ok", _) => ("200 OK", None),
+                    ("HEAD", "/head-not-allowed", _) => ("405 Method Not Allowed", None),
+                    (_, "/head-not-allowed", _) => ("200 OK", None),
+                    (_, _, Some(0)) => ("302 Found", Some("/ok".to_string())),
+                    (_, _, Some(n)) => ("301 Moved Permanently", Some(format!("/hop/{}", n - 1))),
+                    (_, "/loop/a", _) => ("302 Found", Some("/loop/b".to_string())),
+                    (_, "/loop/b", _) => ("302 Found", Some("a".to_string())),
+                    _ => ("404 Not Found", None),
+                };
+                let location = location.map_or_else(String::new, |location| format!("Location: {}\r\n", location));
+                let response = format!(
+                    "HTTP/1.1 {}\r\n{}Content-Length: 0\r\nConnection: close\r\n\r\n",
+                    status, location
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        Url::parse(&format!("http://{}/", address)).unwrap()
+    }
+
+    async fn check_path(base: &Url, path: &str) -> Result<Preflight, SeoError> {
+        check(&CheckRequest::default(), &base.join(path).unwrap()).await
+    }
+
+    #[tokio::test]
+    async fn fails_pages_that_answer_with_errors() {
+        let base = serve().await;
+        assert_eq!(check_path(&base, "/ok").await, Ok(Preflight::default()));
+        assert_eq!(check_path(&base, "/head-not-allowed").await, Ok(Preflight::default()));
+
+        let error = check_path(&base, "/missing").await.unwrap_err();
+        assert!(matches!(error, SeoError::Unreachable { status: Some(404), .. }), "{:?}", error);
+        assert!(!error.is_transient());
+
+        let closed = Url::parse("http://127.0.0.1:9/").unwrap();
+        let error = check(&CheckRequest::default(), &closed).await.unwrap_err();
+        assert!(matches!(error, SeoError::Unreachable { status: None, .. }), "{:?}", error);
+    }
+
+    #[tokio::test]
+    async fn reports_redirect_chains() {
+        let base = serve().await;
+        let hops = |preflight: &Preflight| {
+            let hops = preflight.redirect_chain.iter();
+            hops.map(|(url, status)| (Url::parse(url).unwrap().path().to_string(), *status))
+                .collect::<Vec<_>>()
+        };
+
+        let short = check_path(&base, "/hop/1").await.unwrap();
+        assert_eq!(hops(&short), [("/hop/1".to_string(), 301), ("/hop/0".to_string(), 302)]);
+        assert_eq!(short.warning, None);
+
+        let long = check_path(&base, "/hop/3").await.unwrap();
+        assert_eq!(long.redirect_chain.len(), 4);
+        assert!(long.warning.unwrap().starts_with("4 redirects before reaching"));
+
+        let looped = check_path(&base, "/loop/a").await.unwrap();
+        assert_eq!(hops(&looped), [("/loop/a".to_string(), 302), ("/loop/b".to_string(), 302)]);
+        assert!(looped.warning.unwrap().starts_with("Redirect loop"));
+
+        let endless = check_path(&base, "/hop/20").await.unwrap();
+        assert_eq!(endless.redirect_chain.len(), MAX_REDIRECTS);
+        assert!(endless.warning.unwrap().starts_with("Stopped following redirects after 10"));
+    }
+}
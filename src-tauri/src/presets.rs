@@ -0,0 +1,455 @@
+use crate::{cli, config, json_store};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use tauri::{AppHandle, State};
+
+const PRESETS_FILE_NAME: &str = "presets.json";
+
+/// Every checker category the CLI runs, in report order.
+pub const ALL_CHECKS: &[&str] = &[
+    "metaTags",
+    "headings",
+    "images",
+    "performance",
+    "robotsTxt",
+    "sitemap",
+    "security",
+    "structuredData",
+    "socialMedia",
+    "content",
+    "links",
+    "uiElements",
+    "technical",
+    "accessibility",
+    "urlFactors",
+    "spamDetection",
+    "pageQuality",
+    "advancedImages",
+    "multimedia",
+    "coreWebVitals",
+    "analytics",
+    "mobileUX",
+    "schemaValidation",
+    "resourceOptimization",
+    "legalCompliance",
+    "ecommerce",
+    "internationalization",
+    "heatmap",
+];
+
+/// The CLI version `ALL_CHECKS` was last brought in line with. Older CLIs
+/// may not have every checker.
+const CHECKS_VERSION: Version = Version::new(1, 2, 0);
+
+/// Categories left enabled by the basic preset (see `src/config/presets.ts`).
+const BASIC_CHECKS: &[&str] = &[
+    "metaTags",
+    "headings",
+    "images",
+    "performance",
+    "robotsTxt",
+    "sitemap",
+    "security",
+    "content",
+    "links",
+    "uiElements",
+    "technical",
+    "accessibility",
+    "urlFactors",
+    "heatmap",
+];
+
+/// A named configuration the frontend can offer in its preset picker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Preset {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Checker categories this preset runs.
+    pub checks: Vec<String>,
+    /// False for presets the user saved.
+    pub builtin: bool,
+    /// The config a saved preset runs; built-in presets are selected by id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<Value>,
+}
+
+impl Preset {
+    fn new(id: &str, name: &str, description: &str, checks: &[&str]) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            checks: checks.iter().map(|check| check.to_string()).collect(),
+            builtin: true,
+            config: None,
+        }
+    }
+
+    fn custom(name: &str, config: Value) -> Self {
+        let base = config.get("preset").and_then(Value::as_str);
+        Self {
+            id: name.to_string(),
+            name: name.to_string(),
+            description: match base {
+                Some(base) => format!("Custom configuration based on the {} preset.", base),
+                None => "Custom configuration.".to_string(),
+            },
+            checks: enabled_checks(&config),
+            builtin: false,
+            config: Some(config),
+        }
+    }
+}
+
+/// Whether a config runs `checker`, as the CLI's `ConfigLoader` decides it:
+/// the base preset's choice (every checker without one) unless
+/// `rules.<checker>` overrides it. `false` turns a checker off, while `true`
+/// or an object of rules turns it on.
+pub fn checker_enabled(config: &Value, checker: &str) -> bool {
+    match config.get("rules").and_then(|rules| rules.get(checker)) {
+        Some(Value::Bool(enabled)) => *enabled,
+        Some(_) => true,
+        None => match config.get("preset").and_then(Value::as_str) {
+            Some("basic") => BASIC_CHECKS.contains(&checker),
+            _ => true,
+        },
+    }
+}
+
+/// The checker categories a config runs.
+fn enabled_checks(config: &Value) -> Vec<String> {
+    ALL_CHECKS
+        .iter()
+        .filter(|check| checker_enabled(config, check))
+        .map(|check| check.to_string())
+        .collect()
+}
+
+/// The presets built into the CLI.
+pub fn builtin_presets() -> Vec<Preset> {
+    vec![
+        Preset::new(
+            "basic",
+            "Basic",
+            "Essential on-page checks only: meta tags, headings, images, HTTPS and load time. \
+             Fastest option, suited to quick checks during development.",
+            BASIC_CHECKS,
+        ),
+        Preset::new(
+            "advanced",
+            "Advanced",
+            "Comprehensive audit running every checker with balanced severities. \
+             Recommended for production websites.",
+            ALL_CHECKS,
+        ),
+        Preset::new(
+            "strict",
+            "Strict",
+            "Every checker with strict severities, so most issues are reported as errors. \
+             Suited to enterprise sites and critical launches.",
+            ALL_CHECKS,
+        ),
+    ]
+}
+
+/// Presets saved by the user, kept in `presets.json` in the app config
+/// directory.
+pub struct PresetStore {
+    path: PathBuf,
+    presets: Mutex<Vec<Preset>>,
+}
+
+impl PresetStore {
+    /// Loads the saved presets in `dir`, starting empty if there are none
+    /// or the file is corrupt.
+    pub fn open(dir: &Path) -> Result<Self, String> {
+        let path = dir.join(PRESETS_FILE_NAME);
+        let presets = json_store::load(&path)?;
+
+        Ok(Self {
+            path,
+            presets: Mutex::new(presets),
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Preset>> {
+        self.presets.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn save(&self, presets: &[Preset]) -> Result<(), String> {
+        json_store::save(&self.path, presets)
+    }
+
+    /// Saves `config` under `name`, replacing a saved preset of that name.
+    /// `config` is an inline config or the path to a config file, which is
+    /// read each time the preset runs.
+    pub fn save_preset(&self, name: &str, config: Value) -> Result<Preset, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Preset name is empty".to_string());
+        }
+        // Differing only in case would still be confusing in the picker
+        if builtin_presets()
+            .iter()
+            .any(|preset| preset.id.eq_ignore_ascii_case(name))
+        {
+            return Err(format!("'{}' is a built-in preset and can't be overwritten", name));
+        }
+
+        let (config, loaded) = match config {
+            Value::String(path) => {
+                let path = path.trim();
+                // Would be read as a CLI flag when the preset runs
+                if path.starts_with('-') {
+                    return Err(format!("Preset config path must not start with '-' ({})", path));
+                }
+                // Checks may run from another working directory
+                let path = std::fs::canonicalize(path)
+                    .map_err(|e| format!("Preset config file {} can't be read: {}", path, e))?;
+                let path = path.to_string_lossy().into_owned();
+                let loaded = config::load_config(&path)?;
+                (Value::String(path), loaded)
+            }
+            config => (config.clone(), config),
+        };
+        let validation = config::validate(&loaded);
+        if !validation.valid {
+            return Err(format!("Invalid preset config: {}", validation.errors.join("; ")));
+        }
+
+        let preset = Preset {
+            config: Some(config),
+            ..Preset::custom(name, loaded)
+        };
+        let mut presets = self.lock();
+        match presets.iter_mut().find(|saved| saved.id == preset.id) {
+            Some(saved) => *saved = preset.clone(),
+            None => presets.push(preset.clone()),
+        }
+        self.save(&presets)?;
+        Ok(preset)
+    }
+
+    pub fn delete_preset(&self, name: &str) -> Result<(), String> {
+        if builtin_presets().iter().any(|preset| preset.id == name) {
+            return Err(format!("'{}' is a built-in preset and can't be deleted", name));
+        }
+
+        let mut presets = self.lock();
+        let count = presets.len();
+        presets.retain(|preset| preset.id != name);
+        if presets.len() == count {
+            return Err(format!("No saved preset named '{}'", name));
+        }
+        self.save(&presets)
+    }
+
+    pub fn list(&self) -> Vec<Preset> {
+        self.lock().clone()
+    }
+
+    /// Deletes every saved preset, returning how many there were.
+    pub fn clear(&self) -> Result<usize, String> {
+        let mut presets = self.lock();
+        let count = presets.len();
+        presets.clear();
+        self.save(&presets)?;
+        Ok(count)
+    }
+}
+
+/// What `verify_preset` found wrong with a preset.
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetStatus {
+    pub name: String,
+    /// False when checks with this preset would fail because of its config.
+    pub valid: bool,
+    pub errors: Vec<String>,
+    /// Problems the CLI works around, such as keys it ignores.
+    pub warnings: Vec<String>,
+    /// The CLI version checked against, if it could be determined.
+    pub cli_version: Option<String>,
+}
+
+/// Checks that `preset`'s config can be read and is valid, and that the
+/// CLI at `cli_version` knows the checkers it configures.
+fn verify(preset: &Preset, cli_version: Result<Version, String>) -> PresetStatus {
+    let mut status = PresetStatus {
+        name: preset.name.clone(),
+        valid: true,
+        errors: Vec::new(),
+        warnings: Vec::new(),
+        cli_version: cli_version.as_ref().ok().map(Version::to_string),
+    };
+    if let Err(e) = &cli_version {
+        status.warnings.push(format!("Could not check against the CLI version: {}", e));
+    }
+    // Built-in presets come with the CLI, so whatever it has is current
+    if preset.builtin {
+        return status;
+    }
+
+    // A preset saved from a config file reads it again, as checks do
+    let config = match &preset.config {
+        Some(Value::String(path)) => config::load_config(path),
+        Some(config) => Ok(config.clone()),
+        None => Err("The preset has no config".to_string()),
+    };
+    let config = match config {
+        Ok(config) => config,
+        Err(e) => {
+            status.valid = false;
+            status.errors.push(e);
+            return status;
+        }
+    };
+
+    let validation = config::validate(&config);
+    status.valid = validation.valid;
+    status.errors = validation.errors;
+    status
+        .warnings
+        .extend(validation.unknown_keys.iter().map(|key| format!("Unknown key {}, ignored by the CLI", key)));
+
+    if let Some(version) = cli_version.ok().filter(|version| *version < CHECKS_VERSION) {
+        let configured: Vec<&str> = config
+            .get("rules")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flat_map(|rules| rules.keys())
+            .map(String::as_str)
+            .filter(|checker| ALL_CHECKS.contains(checker))
+            .collect();
+        if !configured.is_empty() {
+            status.warnings.push(format!(
+                "The CLI is version {}, older than {}, and may not know these checkers: {}",
+                version,
+                CHECKS_VERSION,
+                configured.join(", ")
+            ));
+        }
+    }
+    status
+}
+
+/// Built-in presets followed by the user's saved ones.
+#[tauri::command]
+pub async fn get_available_presets(presets: State<'_, PresetStore>) -> Result<Vec<Preset>, String> {
+    let mut available = builtin_presets();
+    available.extend(presets.list());
+    Ok(available)
+}
+
+#[tauri::command]
+pub async fn save_preset(
+    name: String,
+    config: Value,
+    presets: State<'_, PresetStore>,
+) -> Result<Preset, String> {
+    presets.save_preset(&name, config)
+}
+
+#[tauri::command]
+pub async fn verify_preset(
+    app: AppHandle,
+    name: String,
+    presets: State<'_, PresetStore>,
+) -> Result<PresetStatus, String> {
+    let preset = builtin_presets()
+        .into_iter()
+        .chain(presets.list())
+        .find(|preset| preset.id == name)
+        .ok_or_else(|| format!("No preset named '{}'", name))?;
+    let cli_version = cli::cli_version(&app).await.map_err(|e| e.to_string());
+    Ok(verify(&preset, cli_version))
+}
+
+#[tauri::command]
+pub async fn delete_preset(name: String, presets: State<'_, PresetStore>) -> Result<(), String> {
+    presets.delete_preset(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn saves_and_deletes_custom_presets() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = PresetStore::open(dir.path()).unwrap();
+
+        let config = json!({ "preset": "basic", "rules": { "heatmap": false, "analytics": { "enabled": true } } });
+        let saved = store.save_preset(" Marketing ", config).unwrap();
+        assert_eq!(saved.id, "Marketing");
+        assert!(!saved.builtin);
+        assert!(saved.checks.contains(&"analytics".to_string()));
+        assert!(!saved.checks.contains(&"heatmap".to_string()));
+        assert!(!saved.checks.contains(&"ecommerce".to_string()));
+
+        assert!(store.save_preset("Strict", json!({})).unwrap_err().contains("built-in"));
+        assert!(store.save_preset("Broken", json!({ "severity": "loud" })).is_err());
+        assert!(store.delete_preset("basic").is_err());
+
+        let reopened = PresetStore::open(dir.path()).unwrap();
+        assert_eq!(reopened.list().len(), 1);
+        reopened.delete_preset("Marketing").unwrap();
+        assert!(reopened.delete_preset("Marketing").is_err());
+        assert!(PresetStore::open(dir.path()).unwrap().list().is_empty());
+    }
+
+    #[test]
+    fn flags_broken_and_outdated_presets() {
+        let saved = Preset::custom(
+            "Shop",
+            json!({ "rules": { "ecommerce": true, "checkout": false }, "colour": "blue" }),
+        );
+        let status = verify(&saved, Ok(CHECKS_VERSION));
+        assert!(status.valid);
+        assert_eq!(status.cli_version.as_deref(), Some("1.2.0"));
+        assert_eq!(
+            status.warnings,
+            ["Unknown key colour, ignored by the CLI", "Unknown key rules.checkout, ignored by the CLI"]
+        );
+
+        let status = verify(&saved, Ok(Version::new(1, 0, 0)));
+        assert!(status.warnings[2].ends_with("may not know these checkers: ecommerce"));
+
+        let moved = Preset {
+            config: Some(json!("/nonexistent/seo-config.yaml")),
+            ..saved.clone()
+        };
+        let status = verify(&moved, Err("Node.js was not found".to_string()));
+        assert!(!status.valid);
+        assert!(status.errors[0].contains("/nonexistent/seo-config.yaml"));
+        assert!(status.warnings[0].contains("Node.js was not found"));
+
+        let builtin = &builtin_presets()[0];
+        assert!(verify(builtin, Ok(Version::new(1, 0, 0))).warnings.is_empty());
+    }
+
+    #[test]
+    fn flags_presets_whose_config_file_is_gone() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = PresetStore::open(dir.path()).unwrap();
+        let file = dir.path().join("seo-config.yaml");
+        std::fs::write(&file, "preset: basic\nrules:\n  images: false\n").unwrap();
+
+        let saved = store.save_preset("Landing pages", json!(file.to_str().unwrap())).unwrap();
+        assert!(saved.config.as_ref().and_then(Value::as_str).unwrap().ends_with("seo-config.yaml"));
+        assert!(!saved.checks.contains(&"images".to_string()));
+        assert!(verify(&saved, Ok(CHECKS_VERSION)).valid);
+
+        std::fs::remove_file(&file).unwrap();
+        let status = verify(&saved, Ok(CHECKS_VERSION));
+        assert!(!status.valid);
+        assert!(status.errors[0].contains("seo-config.yaml"), "{:?}", status.errors);
+
+        assert!(store.save_preset("Gone", json!(file.to_str().unwrap())).unwrap_err().contains("can't be read"));
+        assert!(store.save_preset("Flag", json!("--output")).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+/// Cancellation tokens of running checks, keyed by the check id supplied by
+/// the frontend.
+///
+/// Each check owns its CLI `Child` and waits on it alongside its token, so
+/// `cancel_seo_check` only has to cancel the token: the check then kills the
+/// child and fails with `SeoError::Cancelled`. A check is registered for its
+/// whole run, retries included, and removed when its `Registration` drops.
+#[derive(Default)]
+pub struct ProcessRegistry {
+    checks: Mutex<HashMap<String, CancellationToken>>,
+    /// Set on app exit, after which no new checks are accepted.
+    closed: AtomicBool,
+    /// Bumped by every `cancel_running`, so checks queued before it (such
+    /// as the rest of a batch) know not to start.
+    generation: AtomicU64,
+}
+
+/// A check's entry in the registry, removed again when this is dropped.
+pub struct Registration<'a> {
+    registry: &'a ProcessRegistry,
+    check_id: String,
+    token: CancellationToken,
+}
+
+impl Registration<'_> {
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+}
+
+impl Drop for Registration<'_> {
+    fn drop(&mut self) {
+        self.registry.lock().remove(&self.check_id);
+    }
+}
+
+impl ProcessRegistry {
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, CancellationToken>> {
+        self.checks.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Registers a check, refusing an id that is already running or any
+    /// check once `cancel_all` has been called.
+    pub fn register(&self, check_id: &str) -> Result<Registration<'_>, String> {
+        let mut checks = self.lock();
+        if self.closed.load(Ordering::SeqCst) {
+            return Err("The app is shutting down".to_string());
+        }
+        if checks.contains_key(check_id) {
+            return Err(format!("An SEO check with id '{}' is already running", check_id));
+        }
+
+        let token = CancellationToken::new();
+        checks.insert(check_id.to_string(), token.clone());
+        Ok(Registration {
+            registry: self,
+            check_id: check_id.to_string(),
+            token,
+        })
+    }
+
+    /// Cancels a running check.
+    pub fn cancel(&self, check_id: &str) -> Result<(), String> {
+        self.lock()
+            .get(check_id)
+            .ok_or_else(|| format!("No running SEO check with id '{}'", check_id))?
+            .cancel();
+        Ok(())
+    }
+
+    /// Cancels every running check, returning how many weren't cancelled
+    /// already. Checks queued behind them should compare `generation` with
+    /// what it was when they were queued.
+    pub fn cancel_running(&self) -> usize {
+        let checks = self.lock();
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        checks
+            .values()
+            .filter(|token| !token.is_cancelled())
+            .inspect(|token| token.cancel())
+            .count()
+    }
+
+    /// Cancels every running check and stops accepting new ones, for app
+    /// exit. Returns how many were cancelled.
+    pub fn cancel_all(&self) -> usize {
+        self.closed.store(true, Ordering::SeqCst);
+        self.cancel_running()
+    }
+
+    /// Counts the `cancel_running` calls so far.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Blocks until every check has finished, or `timeout` has passed.
+    /// Returns whether the registry is empty.
+    pub fn wait_idle(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if self.lock().is_empty() {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancels_registered_checks_until_they_finish() {
+        let registry = ProcessRegistry::default();
+        let check = registry.register("a").unwrap();
+        assert!(registry.register("a").is_err());
+
+        registry.cancel("a").unwrap();
+        assert!(check.token().is_cancelled());
+        drop(check);
+        assert!(registry.cancel("a").is_err());
+        assert!(registry.wait_idle(Duration::ZERO));
+    }
+
+    #[test]
+    fn cancel_running_stops_current_checks_only() {
+        let registry = ProcessRegistry::default();
+        assert_eq!(registry.cancel_running(), 0);
+
+        let queued_at = registry.generation();
+        let (a, b) = (registry.register("a").unwrap(), registry.register("b").unwrap());
+        registry.cancel("a").unwrap();
+        assert_eq!(registry.cancel_running(), 1);
+        assert!(a.token().is_cancelled() && b.token().is_cancelled());
+        assert_ne!(registry.generation(), queued_at);
+
+        let c = registry.register("c").unwrap();
+        assert!(!c.token().is_cancelled());
+    }
+
+    #[test]
+    fn cancel_all_refuses_new_checks() {
+        let registry = ProcessRegistry::default();
+        let (a, b) = (registry.register("a").unwrap(), registry.register("b").unwrap());
+
+        assert_eq!(registry.cancel_all(), 2);
+        assert!(a.token().is_cancelled() && b.token().is_cancelled());
+        assert!(registry.register("c").is_err());
+        assert!(!registry.wait_idle(Duration::ZERO));
+    }
+}
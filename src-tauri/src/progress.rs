@@ -0,0 +1,116 @@
+use regex::Regex;
+use serde::Serialize;
+use std::sync::{Mutex, PoisonError};
+use tauri::State;
+
+/// Matches CLI lines such as `Checking 12/40 pages`.
+const DEFAULT_PATTERN: &str = r"(?P<current>\d+)\s*/\s*(?P<total>\d+)";
+
+/// How far a check has got, parsed from a line of CLI output.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Progress {
+    /// `None` when the line only gave a percentage.
+    pub current: Option<u64>,
+    pub total: Option<u64>,
+    /// 0 to 100.
+    pub percent: f64,
+}
+
+/// The pattern progress lines are matched against, which the frontend can
+/// replace with `set_progress_pattern` when the CLI's wording changes.
+pub struct ProgressPattern {
+    regex: Mutex<Regex>,
+}
+
+impl Default for ProgressPattern {
+    fn default() -> Self {
+        Self {
+            regex: Mutex::new(Regex::new(DEFAULT_PATTERN).expect("default progress pattern is valid")),
+        }
+    }
+}
+
+impl ProgressPattern {
+    /// The current pattern; `Regex` clones share the compiled program.
+    pub fn get(&self) -> Regex {
+        self.regex.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+}
+
+/// Compiles a progress pattern, which needs `current` and `total` named
+/// groups, a `percent` group, or both.
+fn compile(pattern: &str) -> Result<Regex, String> {
+    let regex = Regex::new(pattern).map_err(|e| format!("Invalid progress pattern: {}", e))?;
+    let has_group = |name: &str| regex.capture_names().any(|group| group == Some(name));
+    let has_counts = has_group("current") && has_group("total");
+    if !has_counts && !has_group("percent") {
+        return Err(
+            "Progress pattern needs named groups `current` and `total`, or `percent`".to_string(),
+        );
+    }
+    Ok(regex)
+}
+
+/// Parses a line of CLI output, returning `None` when it doesn't report
+/// progress so the UI can stay indeterminate.
+pub fn parse(regex: &Regex, line: &str) -> Option<Progress> {
+    let captures = regex.captures(line)?;
+    let number = |name: &str| captures.name(name).and_then(|m| m.as_str().parse::<u64>().ok());
+    let current = number("current");
+    let total = number("total");
+
+    let percent = match captures.name("percent") {
+        Some(percent) => percent.as_str().parse::<f64>().ok()?,
+        None => match (current, total) {
+            (Some(current), Some(total)) if total > 0 => current as f64 / total as f64 * 100.0,
+            _ => return None,
+        },
+    };
+
+    Some(Progress {
+        current,
+        total,
+        percent: percent.clamp(0.0, 100.0),
+    })
+}
+
+/// Replaces the progress pattern, or restores the default when `pattern` is `None`.
+#[tauri::command]
+pub async fn set_progress_pattern(
+    pattern: Option<String>,
+    state: State<'_, ProgressPattern>,
+) -> Result<(), String> {
+    let regex = compile(pattern.as_deref().unwrap_or(DEFAULT_PATTERN))?;
+    *state.regex.lock().unwrap_or_else(PoisonError::into_inner) = regex;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_counts_with_the_default_pattern() {
+        let regex = ProgressPattern::default().get();
+        assert_eq!(
+            parse(&regex, "Checking 12/40 pages"),
+            Some(Progress {
+                current: Some(12),
+                total: Some(40),
+                percent: 30.0,
+            })
+        );
+        assert_eq!(parse(&regex, "Launching browser..."), None);
+        assert_eq!(parse(&regex, "Checked 0/0 pages"), None);
+    }
+
+    #[test]
+    fn accepts_percentage_patterns() {
+        let regex = compile(r"(?P<percent>\d+(?:\.\d+)?)% done").unwrap();
+        let progress = parse(&regex, "Audit 62.5% done").unwrap();
+        assert_eq!((progress.current, progress.percent), (None, 62.5));
+
+        assert!(compile(r"(?P<current>\d+) pages").is_err());
+        assert!(compile(r"(unclosed").is_err());
+    }
+}
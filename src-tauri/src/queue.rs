@@ -0,0 +1,227 @@
+use crate::batch::{default_max_concurrent, failed};
+use crate::error::SeoError;
+use crate::process::ProcessRegistry;
+use crate::{execute_check, validate, CheckRequest, SeoCheckResult};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Most checks that may wait in the queue; enqueueing more fails instead of
+/// piling up work the machine won't get to for hours.
+const MAX_QUEUE_SIZE: usize = 500;
+
+static NEXT_JOB_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Counts reported by `get_queue_status`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueueStatus {
+    pub pending: usize,
+    pub running: usize,
+    /// Jobs finished since the app started, successful or not.
+    pub done: usize,
+    pub max_concurrent: usize,
+    pub max_queue_size: usize,
+}
+
+/// Payload of the `queued-check-complete` event, sent when a queued check
+/// has its result.
+#[derive(Debug, Clone, Serialize)]
+struct QueuedCheckEvent {
+    job_id: String,
+    url: String,
+    result: SeoCheckResult,
+}
+
+struct Job {
+    id: String,
+    request: CheckRequest,
+    /// The `ProcessRegistry` generation when the job was enqueued.
+    generation: u64,
+}
+
+#[derive(Default)]
+struct QueueState {
+    pending: VecDeque<Job>,
+    /// Workers taking jobs, each running one check at a time.
+    running: usize,
+    done: usize,
+}
+
+/// Checks waiting for one of at most `max_concurrent` workers. Workers are
+/// started while there are jobs and fewer than `max_concurrent` of them,
+/// and stop once the queue is empty.
+pub struct JobQueue {
+    max_concurrent: usize,
+    max_queue_size: usize,
+    state: Mutex<QueueState>,
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        Self::new(default_max_concurrent(), MAX_QUEUE_SIZE)
+    }
+}
+
+impl JobQueue {
+    fn new(max_concurrent: usize, max_queue_size: usize) -> Self {
+        Self {
+            max_concurrent: max_concurrent.max(1),
+            max_queue_size,
+            state: Mutex::default(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Queues `job`, returning whether a worker should be started for it.
+    fn push(&self, job: Job) -> Result<bool, String> {
+        let mut state = self.lock();
+        if state.pending.len() >= self.max_queue_size {
+            return Err(format!(
+                "The check queue is full ({} checks waiting); try again once some have finished",
+                state.pending.len()
+            ));
+        }
+        state.pending.push_back(job);
+        let start_worker = state.running < self.max_concurrent;
+        if start_worker {
+            state.running += 1;
+        }
+        Ok(start_worker)
+    }
+
+    /// The next job for a worker, or `None` once the queue is empty, in
+    /// which case the worker must stop.
+    fn next(&self) -> Option<Job> {
+        let mut state = self.lock();
+        let job = state.pending.pop_front();
+        if job.is_none() {
+            state.running -= 1;
+        }
+        job
+    }
+
+    fn finish(&self) {
+        self.lock().done += 1;
+    }
+
+    pub fn status(&self) -> QueueStatus {
+        let state = self.lock();
+        QueueStatus {
+            pending: state.pending.len(),
+            running: state.running,
+            done: state.done,
+            max_concurrent: self.max_concurrent,
+            max_queue_size: self.max_queue_size,
+        }
+    }
+}
+
+/// Runs queued checks until there are none left. Jobs enqueued before
+/// `cancel_all_checks` was called are cancelled instead of started.
+async fn work(app: AppHandle) {
+    let queue = app.state::<JobQueue>();
+    while let Some(job) = queue.next() {
+        let url = job.request.url.clone();
+        let result = if app.state::<ProcessRegistry>().generation() != job.generation {
+            SeoCheckResult::localized_failure(&app, SeoError::Cancelled)
+        } else {
+            execute_check(&app, job.request).await.unwrap_or_else(failed)
+        };
+        queue.finish();
+        let _ = app.emit(
+            "queued-check-complete",
+            QueuedCheckEvent {
+                job_id: job.id,
+                url,
+                result,
+            },
+        );
+    }
+}
+
+#[tauri::command]
+pub async fn enqueue_check(
+    app: AppHandle,
+    url: String,
+    config: Option<serde_json::Value>,
+    queue: State<'_, JobQueue>,
+) -> Result<String, String> {
+    validate::normalize_url(&url).map_err(|e| format!("Invalid URL: {}", e))?;
+
+    let id = format!("job-{}", NEXT_JOB_SEQ.fetch_add(1, Ordering::Relaxed));
+    let job = Job {
+        id: id.clone(),
+        request: CheckRequest {
+            url,
+            config,
+            // So `cancel_seo_check` and the progress events know it by its job id
+            check_id: Some(id.clone()),
+            ..CheckRequest::default()
+        },
+        generation: app.state::<ProcessRegistry>().generation(),
+    };
+    if queue.push(job)? {
+        tauri::async_runtime::spawn(work(app.clone()));
+    }
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn get_queue_status(queue: State<'_, JobQueue>) -> Result<QueueStatus, String> {
+    Ok(queue.status())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: &str) -> Job {
+        Job {
+            id: id.to_string(),
+            request: CheckRequest::default(),
+            generation: 0,
+        }
+    }
+
+    #[test]
+    fn caps_workers_and_rejects_jobs_when_full() {
+        let queue = JobQueue::new(2, 3);
+        assert!(queue.push(job("a")).unwrap());
+        assert!(queue.push(job("b")).unwrap());
+        assert!(!queue.push(job("c")).unwrap());
+
+        // Both workers take a job, leaving one waiting
+        assert_eq!(queue.next().unwrap().id, "a");
+        assert_eq!(queue.next().unwrap().id, "b");
+        assert!(!queue.push(job("d")).unwrap());
+        assert!(!queue.push(job("e")).unwrap());
+        assert!(queue.push(job("f")).unwrap_err().contains("queue is full"));
+        assert_eq!(queue.status().pending, 3);
+
+        queue.finish();
+        queue.finish();
+        for id in ["c", "d", "e"] {
+            assert_eq!(queue.next().unwrap().id, id);
+            queue.finish();
+        }
+        assert!(queue.next().is_none());
+        assert!(queue.next().is_none());
+        assert_eq!(
+            queue.status(),
+            QueueStatus {
+                pending: 0,
+                running: 0,
+                done: 5,
+                max_concurrent: 2,
+                max_queue_size: 3,
+            }
+        );
+        // With no workers left, the next job starts one
+        assert!(queue.push(job("g")).unwrap());
+    }
+}
@@ -0,0 +1,449 @@
+use crate::error::SeoError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Shape of the CLI's `--json` output, checked before it is deserialized.
+const REPORT_SCHEMA: &str = include_str!("../schemas/seo-report.schema.json");
+
+/// How many schema errors a `SchemaMismatch` lists.
+const MAX_SCHEMA_ERRORS: usize = 20;
+
+/// Lowest score for each letter grade, best first, with the label shown
+/// alongside it.
+const GRADE_THRESHOLDS: &[(f64, &str, &str)] = &[
+    (90.0, "A", "Good"),
+    (80.0, "B", "Good"),
+    (70.0, "C", "Needs work"),
+    (60.0, "D", "Needs work"),
+    (f64::NEG_INFINITY, "F", "Poor"),
+];
+
+/// The letter grade and label for a 0-100 score, or `None` if it isn't a number.
+pub fn grade(score: f64) -> Option<(&'static str, &'static str)> {
+    GRADE_THRESHOLDS
+        .iter()
+        .find(|(min, _, _)| score >= *min)
+        .map(|(_, letter, label)| (*letter, *label))
+}
+
+/// The CLI's score. Older CLIs print a plain number out of 100, newer ones
+/// `{ "value": 85, "max": 100 }`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "CliScore")]
+pub struct Score {
+    pub value: f64,
+    pub max: f64,
+}
+
+impl Score {
+    /// The score out of 100, as grades, history and diffs compare it.
+    pub fn percent(self) -> f64 {
+        self.value / self.max * 100.0
+    }
+}
+
+impl std::fmt::Display for Score {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.0}/{:.0}", self.value, self.max)
+    }
+}
+
+/// Either shape of `Score` in the CLI's JSON.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CliScore {
+    Number(f64),
+    Object {
+        value: f64,
+        #[serde(default = "default_max_score")]
+        max: f64,
+    },
+}
+
+fn default_max_score() -> f64 {
+    100.0
+}
+
+impl TryFrom<CliScore> for Score {
+    type Error = String;
+
+    fn try_from(score: CliScore) -> Result<Self, Self::Error> {
+        let (value, max) = match score {
+            CliScore::Number(value) => (value, default_max_score()),
+            CliScore::Object { value, max } => (value, max),
+        };
+        if max > 0.0 {
+            Ok(Score { value, max })
+        } else {
+            Err(format!("score max must be positive, got {}", max))
+        }
+    }
+}
+
+/// Severity the CLI attaches to a check (`RuleSeverity` in the CLI).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "info" => Ok(Severity::Info),
+            "warning" => Ok(Severity::Warning),
+            "error" => Ok(Severity::Error),
+            _ => Err(format!("Unknown severity: {}. Use info, warning or error", s)),
+        }
+    }
+}
+
+/// Whether a check with `severity` is at least `min`. Checks without a
+/// severity count as info.
+fn at_least(severity: Option<Severity>, min: Severity) -> bool {
+    severity.unwrap_or(Severity::Info) >= min
+}
+
+/// One check result, mirroring `SEOCheckResult` in the CLI.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckResult {
+    pub passed: bool,
+    #[serde(default)]
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+    /// Check-specific measurements, e.g. title length or the broken links found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl CheckResult {
+    /// `"pass"` or `"fail"`, as shown in exported reports.
+    pub fn status(&self) -> &'static str {
+        if self.passed {
+            "pass"
+        } else {
+            "fail"
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportSummary {
+    #[serde(default)]
+    pub total: u32,
+    #[serde(default)]
+    pub passed: u32,
+    #[serde(default)]
+    pub failed: u32,
+}
+
+/// Per-check milliseconds from a `timings` object in the CLI's report,
+/// slowest first. Entries that aren't a number of milliseconds are skipped.
+fn cli_timings(value: &serde_json::Value) -> Vec<(String, u64)> {
+    let Some(timings) = value.get("timings").and_then(|timings| timings.as_object()) else {
+        return Vec::new();
+    };
+    let mut timings: Vec<(String, u64)> = timings
+        .iter()
+        .filter_map(|(check, ms)| {
+            let ms = ms.as_f64().filter(|ms| ms.is_finite() && *ms >= 0.0)?;
+            Some((check.clone(), ms.round() as u64))
+        })
+        .collect();
+    timings.sort_by(|(a_check, a_ms), (b_check, b_ms)| b_ms.cmp(a_ms).then_with(|| a_check.cmp(b_check)));
+    timings
+}
+
+/// The CLI's JSON report, mirroring `SEOReport` in `src/types/index.ts`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeoReport {
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub timestamp: String,
+    #[serde(default)]
+    pub score: Option<Score>,
+    /// Letter grade (A-F) for `score`, `None` when there is no score.
+    #[serde(default)]
+    pub grade: Option<String>,
+    /// "Good", "Needs work" or "Poor", alongside `grade`.
+    #[serde(default)]
+    pub grade_label: Option<String>,
+    #[serde(default)]
+    pub summary: ReportSummary,
+    /// Check results keyed by checker category, e.g. `metaTags`.
+    #[serde(default)]
+    pub checks: BTreeMap<String, Vec<CheckResult>>,
+    /// Milliseconds each check took, slowest first. The CLI doesn't time
+    /// its checks yet, so this is usually just the whole run as `total`.
+    /// `from_value` reads it from the CLI's `timings` object instead.
+    #[serde(default)]
+    pub timings: Vec<(String, u64)>,
+    /// The untouched CLI output, including fields not modelled above.
+    #[serde(default)]
+    pub raw: serde_json::Value,
+}
+
+impl SeoReport {
+    /// Reads a report as the CLI printed it.
+    pub fn from_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        // The CLI's `timings` is an object of milliseconds, not ours
+        let mut fields = value.clone();
+        if let Some(fields) = fields.as_object_mut() {
+            fields.remove("timings");
+        }
+        let mut report: SeoReport = serde_json::from_value(fields)?;
+        let grade = report.score.map(Score::percent).and_then(grade);
+        report.grade = grade.map(|(letter, _)| letter.to_string());
+        report.grade_label = grade.map(|(_, label)| label.to_string());
+        report.timings = cli_timings(&value);
+        report.raw = value;
+        Ok(report)
+    }
+
+    /// Falls back to timing the whole run when the CLI didn't time its
+    /// checks.
+    pub fn set_total_time(&mut self, ms: u64) {
+        if self.timings.is_empty() {
+            self.timings = vec![("total".to_string(), ms)];
+        }
+    }
+
+    /// Parses the JSON a successful CLI run printed. Invalid UTF-8 is an
+    /// error of its own, since replacing it would only surface as a
+    /// confusing JSON error somewhere later in the text. So is JSON that
+    /// doesn't match the bundled report schema, which usually means the CLI
+    /// and these structs have drifted apart.
+    pub fn from_cli_output(stdout: Vec<u8>) -> Result<Self, SeoError> {
+        let stdout = String::from_utf8(stdout).map_err(|e| SeoError::CliOutputEncoding {
+            offset: e.utf8_error().valid_up_to(),
+        })?;
+        let value: serde_json::Value = serde_json::from_str(&stdout)
+            .map_err(|e| SeoError::JsonParseError { message: e.to_string() })?;
+
+        let errors = schema_errors(&value);
+        if !errors.is_empty() {
+            return Err(SeoError::SchemaMismatch { errors, raw: value });
+        }
+        Self::from_value(value).map_err(|e| SeoError::JsonParseError { message: e.to_string() })
+    }
+
+    /// Drops the checks below `min`, from `checks` and from `raw`, so large
+    /// reports stay small on their way to the frontend. `score` and
+    /// `summary` still cover every check; categories left without checks
+    /// stay, empty.
+    pub fn retain_severity(&mut self, min: Severity) {
+        for results in self.checks.values_mut() {
+            results.retain(|result| at_least(result.severity, min));
+        }
+
+        let Some(categories) = self.raw.get_mut("checks").and_then(|checks| checks.as_object_mut()) else {
+            return;
+        };
+        for results in categories.values_mut().filter_map(|results| results.as_array_mut()) {
+            results.retain(|result| {
+                let severity = result.get("severity").and_then(|severity| severity.as_str());
+                at_least(severity.and_then(|severity| severity.parse().ok()), min)
+            });
+        }
+    }
+}
+
+/// Where and how `value` breaks the report schema, as `/path: message`.
+fn schema_errors(value: &serde_json::Value) -> Vec<String> {
+    static VALIDATOR: OnceLock<jsonschema::Validator> = OnceLock::new();
+    let validator = VALIDATOR.get_or_init(|| {
+        let schema = serde_json::from_str(REPORT_SCHEMA).expect("bundled report schema is valid JSON");
+        jsonschema::validator_for(&schema).expect("bundled report schema is a valid schema")
+    });
+
+    validator
+        .iter_errors(value)
+        .take(MAX_SCHEMA_ERRORS)
+        .map(|error| {
+            let path = error.instance_path.to_string();
+            format!("{}: {}", if path.is_empty() { "/" } else { &path }, error)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_cli_report_and_keeps_raw() {
+        let value = json!({
+            "url": "https://example.com/",
+            "timestamp": "2024-01-01T00:00:00.000Z",
+            "score": 85,
+            "summary": { "total": 2, "passed": 1, "failed": 1 },
+            "checks": {
+                "metaTags": [
+                    { "passed": true, "message": "Title exists", "details": { "length": 42 } },
+                    { "passed": false, "message": "Description too short", "severity": "warning" }
+                ]
+            },
+            "futureField": true
+        });
+
+        let report = SeoReport::from_value(value.clone()).unwrap();
+        assert_eq!(report.score, Some(Score { value: 85.0, max: 100.0 }));
+        assert_eq!(report.grade.as_deref(), Some("B"));
+        assert_eq!(report.grade_label.as_deref(), Some("Good"));
+        assert_eq!(report.summary.failed, 1);
+        let meta = &report.checks["metaTags"];
+        assert!(meta[0].passed);
+        assert_eq!(meta[1].severity, Some(Severity::Warning));
+        assert_eq!(report.raw, value);
+    }
+
+    #[test]
+    fn sorts_cli_timings_and_falls_back_to_the_total() {
+        let mut report = SeoReport::from_value(json!({
+            "url": "https://example.com/",
+            "timings": { "images": 120, "metaTags": 15.4, "sitemap": 2300, "headings": "fast" }
+        }))
+        .unwrap();
+        report.set_total_time(5000);
+        let order: Vec<(&str, u64)> = report.timings.iter().map(|(check, ms)| (check.as_str(), *ms)).collect();
+        assert_eq!(order, [("sitemap", 2300), ("images", 120), ("metaTags", 15)]);
+
+        let mut untimed = SeoReport::from_value(json!({ "url": "https://example.com/" })).unwrap();
+        untimed.set_total_time(5000);
+        assert_eq!(untimed.timings, [("total".to_string(), 5000)]);
+
+        // Reports sent back by the frontend keep them
+        let returned: SeoReport = serde_json::from_value(json!(report)).unwrap();
+        assert_eq!(returned.timings, report.timings);
+    }
+
+    #[test]
+    fn accepts_scores_as_numbers_or_objects() {
+        let score = |score: serde_json::Value| {
+            SeoReport::from_value(json!({ "url": "https://example.com/", "score": score }))
+                .map(|report| (report.score, report.grade))
+        };
+        let (plain, grade) = score(json!(85)).unwrap();
+        assert_eq!(plain, Some(Score { value: 85.0, max: 100.0 }));
+        assert_eq!(grade.as_deref(), Some("B"));
+
+        let (object, grade) = score(json!({ "value": 17, "max": 20 })).unwrap();
+        assert_eq!(object.map(Score::percent), Some(85.0));
+        assert_eq!(object.unwrap().to_string(), "17/20");
+        assert_eq!(grade.as_deref(), Some("B"));
+
+        let (without_max, _) = score(json!({ "value": 92.5 })).unwrap();
+        assert_eq!(without_max, Some(Score { value: 92.5, max: 100.0 }));
+
+        assert!(score(json!({ "value": 5, "max": 0 })).is_err());
+        assert!(score(json!("85")).is_err());
+
+        // Both shapes also pass the schema the CLI's output is checked against
+        let mut value = complete_report();
+        value["score"] = json!({ "value": 17, "max": 20 });
+        assert!(SeoReport::from_cli_output(value.to_string().into_bytes()).is_ok());
+        value["score"] = json!({ "max": 20 });
+        assert!(SeoReport::from_cli_output(value.to_string().into_bytes()).is_err());
+        assert_eq!(serde_json::to_value(plain).unwrap(), json!({ "value": 85.0, "max": 100.0 }));
+    }
+
+    #[test]
+    fn grades_scores_by_threshold() {
+        assert_eq!(grade(100.0), Some(("A", "Good")));
+        assert_eq!(grade(90.0), Some(("A", "Good")));
+        assert_eq!(grade(89.9), Some(("B", "Good")));
+        assert_eq!(grade(65.0), Some(("D", "Needs work")));
+        assert_eq!(grade(0.0), Some(("F", "Poor")));
+        assert_eq!(grade(f64::NAN), None);
+    }
+
+    #[test]
+    fn tolerates_missing_fields() {
+        let report = SeoReport::from_value(json!({ "checks": { "images": [{ "passed": false }] } }))
+            .unwrap();
+        assert_eq!(report.score, None);
+        assert_eq!(report.grade, None);
+        assert_eq!(report.summary.total, 0);
+        assert_eq!(report.checks["images"][0].message, "");
+    }
+
+    fn complete_report() -> serde_json::Value {
+        json!({
+            "url": "https://example.com/",
+            "timestamp": "2024-01-01T00:00:00.000Z",
+            "score": 50,
+            "summary": { "total": 1, "passed": 0, "failed": 1 },
+            "checks": { "images": [{ "passed": false, "message": "Image without alt" }] }
+        })
+    }
+
+    #[test]
+    fn rejects_json_that_breaks_the_report_schema() {
+        let mut value = complete_report();
+        value["summary"]["failed"] = json!("one");
+        value["checks"]["images"][0]["severity"] = json!("fatal");
+        value.as_object_mut().unwrap().remove("url");
+
+        let error = SeoReport::from_cli_output(value.to_string().into_bytes()).unwrap_err();
+        let SeoError::SchemaMismatch { errors, raw } = error else {
+            panic!("expected a schema mismatch, got {:?}", error);
+        };
+        assert_eq!(raw, value);
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert!(errors.iter().any(|e| e.starts_with("/summary/failed: ")));
+        assert!(errors.iter().any(|e| e.starts_with("/checks/images/0/severity: ")));
+        assert!(errors.iter().any(|e| e.starts_with("/: ") && e.contains("url")));
+    }
+
+    #[test]
+    fn filters_checks_by_severity() {
+        let mut value = complete_report();
+        value["checks"]["metaTags"] = json!([
+            { "passed": false, "message": "Title missing", "severity": "error" },
+            { "passed": false, "message": "Description short", "severity": "warning" },
+            { "passed": true, "message": "Charset set" }
+        ]);
+        let mut report = SeoReport::from_value(value).unwrap();
+        report.retain_severity("Warning".parse().unwrap());
+
+        let messages = |checks: &[CheckResult]| checks.iter().map(|c| c.message.clone()).collect::<Vec<_>>();
+        assert_eq!(messages(&report.checks["metaTags"]), ["Title missing", "Description short"]);
+        assert!(report.checks["images"].is_empty());
+        assert_eq!(report.raw["checks"]["metaTags"].as_array().unwrap().len(), 2);
+        assert_eq!(report.raw["checks"]["images"], json!([]));
+        assert_eq!(report.score.map(Score::percent), Some(50.0));
+
+        assert!("fatal".parse::<Severity>().unwrap_err().contains("Unknown severity: fatal"));
+    }
+
+    #[test]
+    fn reports_invalid_utf8_with_its_offset() {
+        let mut stdout = complete_report().to_string().into_bytes();
+        assert!(SeoReport::from_cli_output(stdout.clone()).is_ok());
+
+        stdout.splice(10..10, [0xff, 0xfe]);
+        assert_eq!(
+            SeoReport::from_cli_output(stdout).unwrap_err(),
+            SeoError::CliOutputEncoding { offset: 10 }
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use crate::cache::ResultCache;
+use crate::history::HistoryStore;
+use crate::presets::PresetStore;
+use crate::schedule::ScheduleStore;
+use serde::Serialize;
+use tauri::State;
+
+/// What `reset_app_data` deleted.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResetReport {
+    pub history_entries: usize,
+    pub cached_results: usize,
+    pub presets: usize,
+    pub schedules: usize,
+}
+
+/// Deletes the check history, cached results, saved presets and schedules,
+/// as if the app had just been installed. `confirm` must be true, so a
+/// stray call can't wipe anything.
+#[tauri::command]
+pub async fn reset_app_data(
+    confirm: bool,
+    history: State<'_, HistoryStore>,
+    cache: State<'_, ResultCache>,
+    presets: State<'_, PresetStore>,
+    schedules: State<'_, ScheduleStore>,
+) -> Result<ResetReport, String> {
+    if !confirm {
+        return Err("Resetting deletes all history, presets and schedules; pass confirm: true".to_string());
+    }
+
+    let report = ResetReport {
+        history_entries: history.clear()?,
+        cached_results: cache.clear(),
+        presets: presets.clear()?,
+        schedules: schedules.clear()?,
+    };
+    log::info!("Reset app data: {:?}", report);
+    Ok(report)
+}
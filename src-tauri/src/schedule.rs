@@ -0,0 +1,294 @@
+use crate::json_store;
+use crate::{execute_check, validate, CheckRequest, SeoCheckResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+const SCHEDULES_FILE_NAME: &str = "schedules.json";
+
+/// How often the scheduler looks for due jobs.
+const TICK: Duration = Duration::from_secs(5);
+
+/// Shortest allowed interval, so a typo can't hammer a site.
+const MIN_INTERVAL_SECS: u64 = 60;
+
+/// Distinguishes jobs created within the same millisecond.
+static NEXT_JOB_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// A recurring check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    pub id: String,
+    /// Normalized URL to check.
+    pub url: String,
+    pub config: Option<serde_json::Value>,
+    pub interval_secs: u64,
+    /// Unix timestamp (seconds) of the next run.
+    pub next_run_at: i64,
+}
+
+/// Payload of the `scheduled-check-result` event, sent after each scheduled run.
+#[derive(Debug, Clone, Serialize)]
+struct ScheduledCheckEvent {
+    job_id: String,
+    url: String,
+    result: SeoCheckResult,
+}
+
+/// Recurring checks, saved to `schedules.json` in the app data directory so
+/// they survive a restart.
+pub struct ScheduleStore {
+    path: PathBuf,
+    schedules: Mutex<BTreeMap<String, Schedule>>,
+    /// URLs with a scheduled run in flight.
+    running: Mutex<HashSet<String>>,
+    /// Set on app exit to stop the scheduler; saved schedules are kept.
+    stopped: AtomicBool,
+}
+
+impl ScheduleStore {
+    /// Loads the saved schedules in `dir`, starting empty if there are none
+    /// or the file is corrupt.
+    pub fn open(dir: &Path) -> Result<Self, String> {
+        let path = dir.join(SCHEDULES_FILE_NAME);
+        let schedules: Vec<Schedule> = json_store::load(&path)?;
+
+        Ok(Self {
+            path,
+            schedules: Mutex::new(
+                schedules
+                    .into_iter()
+                    .map(|schedule| (schedule.id.clone(), schedule))
+                    .collect(),
+            ),
+            running: Mutex::default(),
+            stopped: AtomicBool::new(false),
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, Schedule>> {
+        self.schedules.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn save(&self, schedules: &BTreeMap<String, Schedule>) -> Result<(), String> {
+        json_store::save(&self.path, &schedules.values().collect::<Vec<_>>())
+    }
+
+    pub fn add(
+        &self,
+        url: String,
+        config: Option<serde_json::Value>,
+        interval_secs: u64,
+    ) -> Result<Schedule, String> {
+        let now = unix_now();
+        let schedule = Schedule {
+            id: format!(
+                "schedule-{}-{}",
+                now_millis(),
+                NEXT_JOB_SEQ.fetch_add(1, Ordering::Relaxed)
+            ),
+            url,
+            config,
+            interval_secs,
+            // The first run happens right away
+            next_run_at: now,
+        };
+
+        let mut schedules = self.lock();
+        schedules.insert(schedule.id.clone(), schedule.clone());
+        self.save(&schedules)?;
+        Ok(schedule)
+    }
+
+    pub fn remove(&self, id: &str) -> Result<(), String> {
+        let mut schedules = self.lock();
+        if schedules.remove(id).is_none() {
+            return Err(format!("No schedule with id '{}'", id));
+        }
+        self.save(&schedules)
+    }
+
+    pub fn list(&self) -> Vec<Schedule> {
+        self.lock().values().cloned().collect()
+    }
+
+    /// Removes every schedule, returning how many there were.
+    pub fn clear(&self) -> Result<usize, String> {
+        let mut schedules = self.lock();
+        let count = schedules.len();
+        schedules.clear();
+        self.save(&schedules)?;
+        Ok(count)
+    }
+
+    /// Returns the schedules due at `now` and moves each one's next run a
+    /// whole interval ahead, skipping runs missed while the app was closed.
+    fn take_due(&self, now: i64) -> Vec<Schedule> {
+        let mut schedules = self.lock();
+        let mut due = Vec::new();
+        for schedule in schedules.values_mut() {
+            if schedule.next_run_at <= now {
+                due.push(schedule.clone());
+                schedule.next_run_at = now + schedule.interval_secs as i64;
+            }
+        }
+
+        if !due.is_empty() {
+            if let Err(e) = self.save(&schedules) {
+                log::warn!("{}", e);
+            }
+        }
+        due
+    }
+
+    /// Marks a run for `url` as in flight, returning false if one already is.
+    fn start_run(&self, url: &str) -> bool {
+        self.running
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(url.to_string())
+    }
+
+    fn finish_run(&self, url: &str) {
+        self.running
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(url);
+    }
+
+    /// Stops the scheduler from starting any more runs.
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis())
+}
+
+/// Starts the background task that runs due schedules.
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(TICK);
+        loop {
+            interval.tick().await;
+            let store = app.state::<ScheduleStore>();
+            if store.stopped.load(Ordering::SeqCst) {
+                log::info!("Scheduler stopped");
+                break;
+            }
+            for schedule in store.take_due(unix_now()) {
+                // A slow check shouldn't pile up runs of the same URL
+                if !store.start_run(&schedule.url) {
+                    log::info!(
+                        "Skipping scheduled check {} of {}: the previous run hasn't finished",
+                        schedule.id,
+                        schedule.url
+                    );
+                    continue;
+                }
+                tauri::async_runtime::spawn(run_scheduled(app.clone(), schedule));
+            }
+        }
+    });
+}
+
+async fn run_scheduled(app: AppHandle, schedule: Schedule) {
+    let request = CheckRequest {
+        url: schedule.url.clone(),
+        config: schedule.config,
+        // Monitoring needs a fresh result every time
+        force_refresh: true,
+        ..CheckRequest::default()
+    };
+    let result = execute_check(&app, request).await;
+    app.state::<ScheduleStore>().finish_run(&schedule.url);
+
+    match result {
+        Ok(result) => {
+            let _ = app.emit(
+                "scheduled-check-result",
+                ScheduledCheckEvent {
+                    job_id: schedule.id,
+                    url: schedule.url,
+                    result,
+                },
+            );
+        }
+        Err(e) => log::warn!("Scheduled check {} failed: {}", schedule.id, e),
+    }
+}
+
+#[tauri::command]
+pub async fn schedule_check(
+    url: String,
+    config: Option<serde_json::Value>,
+    interval_secs: u64,
+    schedules: State<'_, ScheduleStore>,
+) -> Result<String, String> {
+    if interval_secs < MIN_INTERVAL_SECS {
+        return Err(format!("Interval must be at least {} seconds", MIN_INTERVAL_SECS));
+    }
+    let url = validate::normalize_url(&url).map_err(|e| format!("Invalid URL: {}", e))?;
+    Ok(schedules.add(url.to_string(), config, interval_secs)?.id)
+}
+
+#[tauri::command]
+pub async fn cancel_schedule(job_id: String, schedules: State<'_, ScheduleStore>) -> Result<(), String> {
+    schedules.remove(&job_id)
+}
+
+#[tauri::command]
+pub async fn list_schedules(schedules: State<'_, ScheduleStore>) -> Result<Vec<Schedule>, String> {
+    Ok(schedules.list())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn persists_schedules_and_advances_due_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ScheduleStore::open(dir.path()).unwrap();
+        let daily = store.add("https://example.com/".to_string(), None, 86_400).unwrap();
+        let hourly = store.add("https://example.org/".to_string(), None, 3_600).unwrap();
+        assert_ne!(daily.id, hourly.id);
+
+        let now = daily.next_run_at;
+        assert_eq!(store.take_due(now).len(), 2);
+        assert!(store.take_due(now + 60).is_empty());
+        let due = store.take_due(now + 3_600);
+        assert_eq!(due.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), vec![hourly.id.as_str()]);
+
+        store.remove(&hourly.id).unwrap();
+        assert!(store.remove(&hourly.id).is_err());
+
+        let reopened = ScheduleStore::open(dir.path()).unwrap();
+        assert_eq!(reopened.list(), store.list());
+        assert_eq!(reopened.list()[0].next_run_at, now + 86_400);
+    }
+
+    #[test]
+    fn allows_one_run_per_url_at_a_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ScheduleStore::open(dir.path()).unwrap();
+        assert!(store.start_run("https://example.com/"));
+        assert!(!store.start_run("https://example.com/"));
+        store.finish_run("https://example.com/");
+        assert!(store.start_run("https://example.com/"));
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{cli, environment, execute_check, CheckRequest};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::AppHandle;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Page served to the sample check, with the basics a report looks for.
+const SAMPLE_PAGE: &str = "<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"utf-8\">\
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+<title>e2e-seo self-test</title><meta name=\"description\" content=\"Self-test page\">\
+</head><body><h1>e2e-seo self-test</h1></body></html>";
+
+/// Time limit for the sample check, which includes browser startup.
+const SAMPLE_TIMEOUT_SECS: u64 = 60;
+
+/// One step of `self_test`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfTestStep {
+    /// `node`, `cli_file`, `cli_version` or `sample_check`.
+    pub id: String,
+    pub passed: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfTestReport {
+    /// True when every step passed.
+    pub passed: bool,
+    pub steps: Vec<SelfTestStep>,
+}
+
+fn step(id: &str, result: Result<String, String>) -> SelfTestStep {
+    let (passed, message) = match result {
+        Ok(message) => (true, message),
+        Err(message) => (false, message),
+    };
+    SelfTestStep {
+        id: id.to_string(),
+        passed,
+        message,
+    }
+}
+
+async fn check_node(app: &AppHandle) -> Result<String, String> {
+    let status = environment::probe(app).await;
+    if !status.node_required {
+        return Ok("Checks run through the bundled SEO checker, which doesn't need Node.js".to_string());
+    }
+    match (status.message, status.node_version) {
+        (Some(message), _) => Err(message),
+        (None, version) => Ok(format!(
+            "Node.js {} at {}",
+            version.unwrap_or_default(),
+            status.node_path.unwrap_or_else(|| "node".to_string())
+        )),
+    }
+}
+
+fn check_cli_file(app: &AppHandle) -> Result<String, String> {
+    let path = if cli::uses_system_node() {
+        cli::resolve_cli_path(app).map_err(|e| e.to_string())?
+    } else {
+        let cmd = cli::cli_command(app).map_err(|e| e.to_string())?;
+        Path::new(cmd.as_std().get_program()).to_path_buf()
+    };
+
+    if path.is_file() {
+        Ok(format!("Found {}", path.display()))
+    } else {
+        Err(format!("SEO checker not found at {}", path.display()))
+    }
+}
+
+/// Serves `SAMPLE_PAGE` on a local port until the returned task is aborted,
+/// so the sample check needs no network access.
+async fn serve_sample_page() -> Result<(String, tauri::async_runtime::JoinHandle<()>), String> {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(|e| format!("Failed to start the sample page server: {}", e))?;
+    let address = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to start the sample page server: {}", e))?;
+
+    let server = tauri::async_runtime::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tauri::async_runtime::spawn(async move {
+                // Every path gets the page; the request itself doesn't matter
+                let mut request = [0; 4096];
+                let _ = stream.read(&mut request).await;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    SAMPLE_PAGE.len(),
+                    SAMPLE_PAGE
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    Ok((format!("http://{}/", address), server))
+}
+
+async fn check_sample(app: &AppHandle) -> Result<String, String> {
+    let (url, server) = serve_sample_page().await?;
+    let request = CheckRequest {
+        url,
+        timeout_secs: Some(SAMPLE_TIMEOUT_SECS),
+        force_refresh: true,
+        ephemeral: true,
+        ..CheckRequest::default()
+    };
+    let result = execute_check(app, request).await;
+    server.abort();
+
+    let result = result?;
+    match (&result.data, result.error) {
+        (Some(report), None) if result.success => Ok(format!(
+            "Audited a local test page ({} checks)",
+            report.summary.total
+        )),
+        (_, error) => Err(format!(
+            "Sample check failed: {}",
+            error.unwrap_or_else(|| "unknown error".to_string())
+        )),
+    }
+}
+
+/// Checks step by step that audits can run: Node.js when it's needed, the
+/// CLI's file, its `--version`, and a check of a page served locally. The
+/// sample check is skipped when an earlier step failed.
+#[tauri::command]
+pub async fn self_test(app: AppHandle) -> Result<SelfTestReport, String> {
+    let mut steps = vec![
+        step("node", check_node(&app).await),
+        step("cli_file", check_cli_file(&app)),
+        step(
+            "cli_version",
+            cli::query_version(&app)
+                .await
+                .map(|version| format!("SEO checker {}", version))
+                .map_err(|e| e.to_string()),
+        ),
+    ];
+
+    let sample = if steps.iter().all(|step| step.passed) {
+        check_sample(&app).await
+    } else {
+        Err("Skipped because an earlier step failed".to_string())
+    };
+    steps.push(step("sample_check", sample));
+
+    Ok(SelfTestReport {
+        passed: steps.iter().all(|step| step.passed),
+        steps,
+    })
+}
@@ -0,0 +1,222 @@
+use crate::batch;
+use crate::{validate, CheckRequest, SeoCheckResult};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// How many pages a sitemap check audits when the caller doesn't say.
+const DEFAULT_MAX_URLS: usize = 50;
+
+/// How many levels of sitemap indexes are followed below the given sitemap.
+const MAX_INDEX_DEPTH: usize = 3;
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A parsed sitemap file: either page URLs or, for a sitemap index, the URLs
+/// of further sitemaps.
+#[derive(Debug, PartialEq)]
+enum Sitemap {
+    UrlSet(Vec<String>),
+    Index(Vec<String>),
+}
+
+/// Extracts the `<loc>` entries of a sitemap or sitemap index.
+///
+/// Only top-level `<loc>` elements count, so extension entries such as
+/// `<image:loc>` aren't mistaken for pages.
+fn parse_sitemap(xml: &str) -> Result<Sitemap, String> {
+    let to_error = |e: quick_xml::Error| format!("Invalid sitemap XML: {}", e);
+
+    let mut reader = Reader::from_str(xml);
+    let mut is_index = None;
+    let mut locs = Vec::new();
+    let mut loc: Option<String> = None;
+
+    loop {
+        match reader.read_event().map_err(to_error)? {
+            Event::Start(e) if is_index.is_none() => {
+                is_index = Some(match e.name().as_ref() {
+                    b"urlset" => false,
+                    b"sitemapindex" => true,
+                    _ => {
+                        return Err(format!(
+                            "Not a sitemap: expected <urlset> or <sitemapindex>, found <{}>",
+                            String::from_utf8_lossy(e.name().as_ref())
+                        ))
+                    }
+                });
+            }
+            Event::Start(e) if e.name().as_ref() == b"loc" => loc = Some(String::new()),
+            Event::Text(text) => {
+                if let Some(loc) = &mut loc {
+                    loc.push_str(&text.unescape().map_err(to_error)?);
+                }
+            }
+            Event::CData(text) => {
+                if let Some(loc) = &mut loc {
+                    loc.push_str(&String::from_utf8_lossy(&text));
+                }
+            }
+            Event::End(e) if e.name().as_ref() == b"loc" => {
+                if let Some(loc) = loc.take() {
+                    let loc = loc.trim();
+                    if !loc.is_empty() {
+                        locs.push(loc.to_string());
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    match is_index {
+        Some(true) => Ok(Sitemap::Index(locs)),
+        Some(false) => Ok(Sitemap::UrlSet(locs)),
+        None => Err("Not a sitemap: the document is empty".to_string()),
+    }
+}
+
+async fn fetch(client: &reqwest::Client, url: &str) -> Result<String, String> {
+    let to_error = |e: reqwest::Error| format!("Failed to fetch sitemap {}: {}", url, e);
+
+    let response = client.get(url).send().await.map_err(to_error)?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("Failed to fetch sitemap {}: HTTP {}", url, status));
+    }
+    response.text().await.map_err(to_error)
+}
+
+/// Collects up to `max_urls` distinct page URLs from a sitemap, following
+/// sitemap indexes breadth-first.
+///
+/// The given sitemap must load; a nested sitemap that fails is skipped with a
+/// warning so one broken file doesn't lose the rest.
+async fn collect_urls(sitemap_url: &str, max_urls: usize) -> Result<Vec<String>, String> {
+    let client = crate::http::client(FETCH_TIMEOUT)?;
+
+    let mut queue = VecDeque::from([(sitemap_url.to_string(), 0)]);
+    let mut visited = HashSet::new();
+    let mut seen = HashSet::new();
+    let mut urls = Vec::new();
+
+    while let Some((sitemap, depth)) = queue.pop_front() {
+        if urls.len() >= max_urls {
+            break;
+        }
+        if !visited.insert(sitemap.clone()) {
+            continue;
+        }
+
+        let parsed = fetch(&client, &sitemap)
+            .await
+            .and_then(|xml| parse_sitemap(&xml).map_err(|e| format!("{} ({})", e, sitemap)));
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) if depth == 0 => return Err(e),
+            Err(e) => {
+                log::warn!("Skipping nested sitemap: {}", e);
+                continue;
+            }
+        };
+
+        match parsed {
+            Sitemap::UrlSet(locs) => {
+                for loc in locs {
+                    if urls.len() >= max_urls {
+                        break;
+                    }
+                    if seen.insert(loc.clone()) {
+                        urls.push(loc);
+                    }
+                }
+            }
+            Sitemap::Index(locs) if depth < MAX_INDEX_DEPTH => {
+                queue.extend(locs.into_iter().map(|loc| (loc, depth + 1)));
+            }
+            Sitemap::Index(_) => log::warn!("Ignoring sitemap index nested too deeply: {}", sitemap),
+        }
+    }
+
+    Ok(urls)
+}
+
+#[tauri::command]
+pub async fn run_sitemap_check(
+    app: AppHandle,
+    sitemap_url: String,
+    config: Option<serde_json::Value>,
+    max_urls: Option<usize>,
+    throttle_hosts: Option<bool>,
+    host_delay_ms: Option<u64>,
+) -> Result<Vec<(String, SeoCheckResult)>, String> {
+    let sitemap_url = validate::normalize_url(&sitemap_url)
+        .map_err(|e| format!("Invalid sitemap URL: {}", e))?;
+
+    let urls = collect_urls(sitemap_url.as_str(), max_urls.unwrap_or(DEFAULT_MAX_URLS)).await?;
+    if urls.is_empty() {
+        return Err(format!("Sitemap {} lists no URLs", sitemap_url));
+    }
+
+    let requests = urls
+        .into_iter()
+        .map(|url| CheckRequest {
+            url,
+            config: config.clone(),
+            ..CheckRequest::default()
+        })
+        .collect();
+
+    Ok(batch::run_batch(
+        &app,
+        requests,
+        batch::default_max_concurrent(),
+        batch::HostThrottle::from_options(throttle_hosts, host_delay_ms),
+    )
+    .await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_urlsets_and_indexes() {
+        let urlset = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9"
+                    xmlns:image="http://www.google.com/schemas/sitemap-image/1.1">
+              <url>
+                <loc> https://example.com/?a=1&amp;b=2 </loc>
+                <image:image><image:loc>https://example.com/logo.png</image:loc></image:image>
+              </url>
+              <url><loc><![CDATA[https://example.com/about]]></loc></url>
+            </urlset>"#;
+        assert_eq!(
+            parse_sitemap(urlset).unwrap(),
+            Sitemap::UrlSet(vec![
+                "https://example.com/?a=1&b=2".to_string(),
+                "https://example.com/about".to_string()
+            ])
+        );
+
+        let index = r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+              <sitemap><loc>https://example.com/sitemap-posts.xml</loc></sitemap>
+            </sitemapindex>"#;
+        assert_eq!(
+            parse_sitemap(index).unwrap(),
+            Sitemap::Index(vec!["https://example.com/sitemap-posts.xml".to_string()])
+        );
+    }
+
+    #[test]
+    fn rejects_documents_that_are_not_sitemaps() {
+        assert!(parse_sitemap("<html><body>Not found</body></html>")
+            .unwrap_err()
+            .contains("found <html>"));
+        assert!(parse_sitemap("").is_err());
+        assert!(parse_sitemap("<urlset><url><loc>a</url></urlset>").is_err());
+    }
+}
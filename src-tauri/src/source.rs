@@ -0,0 +1,52 @@
+use crate::validate;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Largest page body `fetch_page_source` will load.
+const MAX_SOURCE_BYTES: usize = 5 * 1024 * 1024;
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Fetches a page's raw HTML so the frontend can show it next to the issues
+/// a check found. Redirects are followed; the body is decoded as UTF-8 with
+/// invalid bytes replaced, which is fine for display.
+#[tauri::command]
+pub async fn fetch_page_source(
+    url: String,
+    user_agent: Option<String>,
+    headers: Option<HashMap<String, String>>,
+    proxy: Option<String>,
+) -> Result<String, String> {
+    let url = validate::normalize_url(&url).map_err(|e| format!("Invalid URL: {}", e))?;
+    let client = crate::http::page_client(
+        FETCH_TIMEOUT,
+        user_agent.as_deref(),
+        &headers.unwrap_or_default(),
+        proxy.as_deref(),
+    )?;
+
+    let to_error = |e: reqwest::Error| format!("Failed to fetch {}: {}", url, e);
+    let mut response = client.get(url.clone()).send().await.map_err(to_error)?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("Failed to fetch {}: HTTP {}", url, status));
+    }
+
+    let too_large = || format!("{} is larger than {} MB", url, MAX_SOURCE_BYTES / (1024 * 1024));
+    if response
+        .content_length()
+        .is_some_and(|length| length > MAX_SOURCE_BYTES as u64)
+    {
+        return Err(too_large());
+    }
+
+    // Content-Length can be missing or wrong, so count while reading too
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(to_error)? {
+        if body.len() + chunk.len() > MAX_SOURCE_BYTES {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
@@ -0,0 +1,174 @@
+use crate::{execute_check, validate, CheckRequest, SeoCheckResult};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+/// How long a file must go unchanged before it is checked again. Editors
+/// often save in several steps, such as truncating and then writing.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+static NEXT_WATCH_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Payload of the `watch-result` event, sent after each check of a watched
+/// file.
+#[derive(Debug, Clone, Serialize)]
+struct WatchResultEvent {
+    watch_id: String,
+    file_path: String,
+    result: SeoCheckResult,
+}
+
+/// Local files being watched, by watch id. Dropping a watcher closes its
+/// channel, which ends the task that runs its checks.
+#[derive(Default)]
+pub struct WatchRegistry {
+    watches: Mutex<HashMap<String, RecommendedWatcher>>,
+}
+
+impl WatchRegistry {
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, RecommendedWatcher>> {
+        self.watches.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn contains(&self, id: &str) -> bool {
+        self.lock().contains_key(id)
+    }
+
+    /// Stops every watch, returning how many there were.
+    pub fn stop_all(&self) -> usize {
+        let mut watches = self.lock();
+        let count = watches.len();
+        watches.clear();
+        count
+    }
+}
+
+/// Waits for a change, then until none has arrived for `delay`. Returns
+/// false once the watcher is gone.
+async fn debounced(changes: &mut UnboundedReceiver<()>, delay: Duration) -> bool {
+    if changes.recv().await.is_none() {
+        return false;
+    }
+    loop {
+        match tokio::time::timeout(delay, changes.recv()).await {
+            Ok(Some(())) => continue,
+            Ok(None) => return false,
+            Err(_) => return true,
+        }
+    }
+}
+
+/// Checks the file after each debounced change until the watch is stopped.
+/// Checks run one at a time; changes made during a check start another one
+/// once it finishes.
+async fn run_watch(
+    app: AppHandle,
+    id: String,
+    file_path: String,
+    request: CheckRequest,
+    mut changes: UnboundedReceiver<()>,
+) {
+    while debounced(&mut changes, DEBOUNCE).await {
+        log::debug!("Watched file {} changed, checking it", file_path);
+        let result = execute_check(&app, request.clone()).await;
+        if !app.state::<WatchRegistry>().contains(&id) {
+            break;
+        }
+        match result {
+            Ok(result) => {
+                let _ = app.emit(
+                    "watch-result",
+                    WatchResultEvent {
+                        watch_id: id.clone(),
+                        file_path: file_path.clone(),
+                        result,
+                    },
+                );
+            }
+            Err(e) => log::warn!("Check of watched file {} failed: {}", file_path, e),
+        }
+    }
+    log::info!("Stopped watching {}", file_path);
+}
+
+#[tauri::command]
+pub async fn watch_file(
+    app: AppHandle,
+    file_path: String,
+    config: Option<serde_json::Value>,
+    watches: State<'_, WatchRegistry>,
+) -> Result<String, String> {
+    let url = validate::html_file(&file_path)?;
+    let path = url
+        .to_file_path()
+        .map_err(|_| format!("Not a local file: {}", file_path))?;
+    // Editors that save by replacing the file would leave a watch on the
+    // file itself watching a deleted inode, so watch its directory instead
+    let dir = path.parent().map(PathBuf::from).unwrap_or_else(|| path.clone());
+
+    let (sender, changes) = mpsc::unbounded_channel();
+    let watched = path.clone();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+        Ok(event) => {
+            let changed = event.kind.is_create() || event.kind.is_modify();
+            if changed && event.paths.contains(&watched) {
+                let _ = sender.send(());
+            }
+        }
+        Err(e) => log::warn!("Failed to watch {}: {}", watched.display(), e),
+    })
+    .map_err(|e| format!("Failed to watch {}: {}", path.display(), e))?;
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch {}: {}", path.display(), e))?;
+
+    let id = format!("watch-{}", NEXT_WATCH_SEQ.fetch_add(1, Ordering::Relaxed));
+    watches.lock().insert(id.clone(), watcher);
+    log::info!("Watching {} as {}", path.display(), id);
+
+    let request = CheckRequest {
+        url: url.into(),
+        config,
+        local_file: true,
+        ..CheckRequest::default()
+    };
+    let file_path = path.display().to_string();
+    tauri::async_runtime::spawn(run_watch(app, id.clone(), file_path, request, changes));
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn stop_watch(id: String, watches: State<'_, WatchRegistry>) -> Result<(), String> {
+    match watches.lock().remove(&id) {
+        Some(_) => Ok(()),
+        None => Err(format!("No watch with id '{}'", id)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn waits_for_changes_to_settle() {
+        let (sender, mut changes) = mpsc::unbounded_channel();
+        let burst = sender.clone();
+        tokio::spawn(async move {
+            for _ in 0..3 {
+                burst.send(()).unwrap();
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        });
+
+        // One run for the whole burst
+        assert!(debounced(&mut changes, Duration::from_millis(100)).await);
+        drop(sender);
+        assert!(!debounced(&mut changes, Duration::from_millis(100)).await);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::SeoCheckResult;
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+use url::Url;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// What a webhook receives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    /// The serialized `SeoCheckResult`, or the batch's `[url, result]` pairs.
+    #[default]
+    Json,
+    /// `{ "text": ... }` with a one-line summary per URL, as Slack and
+    /// Discord (with `/slack` appended to its webhook URL) accept.
+    Slack,
+}
+
+/// An endpoint to notify when checks complete.
+pub struct Webhook {
+    url: Url,
+    format: WebhookFormat,
+}
+
+impl Webhook {
+    pub fn new(url: &str, format: WebhookFormat) -> Result<Self, String> {
+        let url = Url::parse(url.trim()).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(format!("Invalid webhook URL: unsupported scheme '{}'", url.scheme()));
+        }
+        Ok(Self { url, format })
+    }
+
+    pub async fn send_result(&self, url: &str, result: &SeoCheckResult) {
+        let payload = match self.format {
+            WebhookFormat::Json => json!(result),
+            WebhookFormat::Slack => json!({ "text": summary_line(url, result) }),
+        };
+        self.post(&payload).await;
+    }
+
+    pub async fn send_batch(&self, results: &[(String, SeoCheckResult)]) {
+        let payload = match self.format {
+            WebhookFormat::Json => json!(results),
+            WebhookFormat::Slack => {
+                let passed = results.iter().filter(|(_, result)| result.success).count();
+                let mut text = format!("SEO checks finished: {} of {} succeeded", passed, results.len());
+                for (url, result) in results {
+                    text.push('\n');
+                    text.push_str(&summary_line(url, result));
+                }
+                json!({ "text": text })
+            }
+        };
+        self.post(&payload).await;
+    }
+
+    /// The URL as logged: webhook URLs usually carry their secret in the
+    /// path or query, so only the origin is shown.
+    fn redacted_url(&self) -> String {
+        if self.url.path() == "/" && self.url.query().is_none() && self.url.password().is_none() {
+            return self.url.to_string();
+        }
+        format!("{}/[redacted]", self.url.origin().ascii_serialization())
+    }
+
+    /// Posts the payload, logging rather than returning failures: a broken
+    /// webhook shouldn't cost the caller its results.
+    async fn post(&self, payload: &serde_json::Value) {
+        let url = self.redacted_url();
+        log::debug!("Posting to webhook {}", url);
+        let response = match crate::http::client(WEBHOOK_TIMEOUT) {
+            Ok(client) => client.post(self.url.clone()).json(payload).send().await,
+            Err(e) => {
+                log::warn!("{}", e);
+                return;
+            }
+        };
+
+        match response {
+            Ok(response) if !response.status().is_success() => {
+                log::warn!("Webhook {} responded with HTTP {}", url, response.status());
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("Failed to call webhook {}: {}", url, e.without_url()),
+        }
+    }
+}
+
+fn summary_line(url: &str, result: &SeoCheckResult) -> String {
+    let Some(report) = result.data.as_ref().filter(|_| result.success) else {
+        return format!(
+            "❌ {}: check failed: {}",
+            url,
+            result.error.as_deref().unwrap_or("unknown error")
+        );
+    };
+
+    let score = match (report.score, &report.grade) {
+        (Some(score), Some(grade)) => format!("score {} ({})", score, grade),
+        (Some(score), None) => format!("score {}", score),
+        (None, _) => "no score".to_string(),
+    };
+    format!(
+        "✅ {}: {}, {} passed, {} failed",
+        url, score, report.summary.passed, report.summary.failed
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SeoError;
+    use crate::report::SeoReport;
+
+    #[test]
+    fn summarizes_results_for_slack() {
+        let report = SeoReport::from_value(json!({
+            "score": 82.4,
+            "summary": { "total": 10, "passed": 8, "failed": 2 }
+        }))
+        .unwrap();
+        let success = SeoCheckResult {
+            success: true,
+            data: Some(report),
+            ..SeoCheckResult::default()
+        };
+        assert_eq!(
+            summary_line("https://example.com/", &success),
+            "✅ https://example.com/: score 82/100 (B), 8 passed, 2 failed"
+        );
+
+        let failure = SeoCheckResult::failure(SeoError::Timeout { secs: 60 });
+        assert_eq!(
+            summary_line("https://example.com/", &failure),
+            "❌ https://example.com/: check failed: SEO check timed out after 60 seconds"
+        );
+    }
+
+    #[test]
+    fn requires_an_http_webhook_url() {
+        assert!(Webhook::new("https://hooks.slack.com/services/T0/B0/x", WebhookFormat::Slack).is_ok());
+        assert!(Webhook::new("ftp://example.com/hook", WebhookFormat::Json).is_err());
+        assert!(Webhook::new("not a url", WebhookFormat::Json).is_err());
+    }
+
+    #[test]
+    fn redacts_webhook_secrets_from_logs() {
+        let redacted = |url| Webhook::new(url, WebhookFormat::Json).unwrap().redacted_url();
+        assert_eq!(
+            redacted("https://hooks.slack.com/services/T0/B0/secret"),
+            "https://hooks.slack.com/[redacted]"
+        );
+        assert_eq!(redacted("http://localhost:9000/?token=abc"), "http://localhost:9000/[redacted]");
+        assert_eq!(redacted("https://ci.example.com"), "https://ci.example.com/");
+    }
+}
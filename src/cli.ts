@@ -3,6 +3,9 @@
 import { SEOChecker } from './index';
 import * as fs from 'fs';
 
+// Read at runtime: package.json sits outside rootDir, so it can't be imported
+const { version } = require('../package.json') as { version: string };
+
 interface CliArgs {
   url?: string;
   output?: string;
@@ -11,8 +14,13 @@ interface CliArgs {
   config?: string;
   preset?: string;
   help?: boolean;
+  version?: boolean;
   initConfig?: boolean;
   json?: boolean;
+  userAgent?: string;
+  headers?: Record<string, string>;
+  proxy?: string;
+  auth?: { username: string; password: string };
 }
 
 function parseArgs(): CliArgs {
@@ -26,6 +34,10 @@ function parseArgs(): CliArgs {
       case '--help':
         args.help = true;
         break;
+      case '-V':
+      case '--version':
+        args.version = true;
+        break;
       case '-u':
       case '--url':
         args.url = argv[++i];
@@ -54,6 +66,42 @@ function parseArgs(): CliArgs {
       case '--json':
         args.json = true;
         break;
+      case '--user-agent':
+        args.userAgent = argv[++i];
+        break;
+      case '--proxy':
+        args.proxy = argv[++i];
+        break;
+      case '--auth': {
+        // Split at the first colon: user ids can't contain one, passwords can
+        const credentials = argv[++i] ?? '';
+        const separator = credentials.indexOf(':');
+        if (separator > 0) {
+          args.auth = {
+            username: credentials.slice(0, separator),
+            password: credentials.slice(separator + 1),
+          };
+        }
+        break;
+      }
+      case '--':
+        // Everything after `--` is positional, even if it starts with `-`
+        if (!args.url) {
+          args.url = argv[i + 1];
+        }
+        i = argv.length;
+        break;
+      case '--header': {
+        const header = argv[++i] ?? '';
+        const separator = header.indexOf(':');
+        if (separator > 0) {
+          args.headers = {
+            ...args.headers,
+            [header.slice(0, separator).trim()]: header.slice(separator + 1).trim(),
+          };
+        }
+        break;
+      }
       default:
         if (!arg.startsWith('-') && !args.url) {
           args.url = arg;
@@ -79,6 +127,11 @@ Options:
   --init-config          Create a default configuration file
   --headed               Run browser in headed mode (default: headless)
   --viewport <WxH>       Set viewport size (e.g., 1920x1080 or 375x667)
+  --user-agent <ua>      Send a custom User-Agent header
+  --header <"K: V">      Send an extra HTTP header (repeatable)
+  --proxy <url>          Route browser traffic through an http, https or socks5 proxy
+  --auth <user:pass>     Answer HTTP basic auth challenges with these credentials
+  -V, --version          Print the version and exit
   -h, --help             Show this help message
 
 Examples:
@@ -126,6 +179,11 @@ For more information, visit: https://github.com/yourusername/e2e-seo
 async function main() {
   const args = parseArgs();
 
+  if (args.version) {
+    console.log(version);
+    process.exit(0);
+  }
+
   // Handle --init-config flag
   if (args.initConfig) {
     const { ConfigLoader } = await import('./config');
@@ -167,6 +225,10 @@ async function main() {
     viewport,
     configFile: args.config,
     config,
+    userAgent: args.userAgent,
+    extraHTTPHeaders: args.headers,
+    proxy: args.proxy,
+    httpCredentials: args.auth,
   });
 
   try {
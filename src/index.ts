@@ -30,6 +30,19 @@ import { EcommerceChecker } from './checkers/ecommerce';
 import { InternationalizationChecker } from './checkers/internationalization';
 import { HeatmapChecker } from './checkers/heatmap';
 
+/**
+ * Converts a proxy URL into Playwright's proxy settings, which take any
+ * credentials embedded in the URL separately.
+ */
+function parseProxy(proxy: string): { server: string; username?: string; password?: string } {
+  const url = new URL(proxy);
+  return {
+    server: `${url.protocol}//${url.host}`,
+    username: url.username ? decodeURIComponent(url.username) : undefined,
+    password: url.password ? decodeURIComponent(url.password) : undefined,
+  };
+}
+
 export class SEOChecker {
   private browser: Browser | null = null;
   private page: Page | null = null;
@@ -117,10 +130,14 @@ export class SEOChecker {
   private async launch(): Promise<void> {
     this.browser = await chromium.launch({
       headless: this.options.headless,
+      proxy: this.options.proxy ? parseProxy(this.options.proxy) : undefined,
     });
 
     this.page = await this.browser.newPage({
       viewport: this.options.viewport,
+      userAgent: this.options.userAgent,
+      extraHTTPHeaders: this.options.extraHTTPHeaders,
+      httpCredentials: this.options.httpCredentials,
     });
 
     await this.page.setDefaultTimeout(this.options.timeout!);